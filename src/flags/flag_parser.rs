@@ -1,4 +1,4 @@
-use clap::{command, crate_name, Parser};
+use clap::{crate_name, Parser};
 
 /// Simple program to greet a person
 #[derive(Parser, Debug, Clone)]
@@ -7,9 +7,14 @@ pub struct CommandLineFlags {
     /// Testing mode is activated
     #[arg(short, long)]
     pub test: bool,
+    /// Convert one format's file into another's without a database, such as `--convert prisma json`
+    #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+    pub convert: Option<Vec<String>>,
+    /// The name of the database whose files are converted, when the session has more than one
+    #[arg(long)]
+    pub database: Option<String>,
 }
 
 pub fn get_command_line_flags() -> CommandLineFlags {
-    let args = CommandLineFlags::parse();
-    args
+    CommandLineFlags::parse()
 }
@@ -1,53 +1,91 @@
-use crate::functionality::structure::{AcceptedFormat, DiskMapping};
-use crate::remotes::sql;
+use crate::functionality::structure::{AcceptedFormat, DiskMapping, RemoteSource};
+use crate::remotes::{
+    behaviour::Remote, ddl::DdlRemote, postgres::PostgresRemote, sql, sql::MySqlRemote,
+    sqlite::SqliteRemote,
+};
 use crate::translators::{
-    behaviour::TranslatorBehaviour, json_translator::JsonTranslator,
-    prisma_translator::PrismaTranslator,
+    behaviour::Translator,
+    json::shape::JsonShape,
+    ordering::TableOrder,
+    prisma::{naming::NamingPolicy, options::PrismaOptions},
+    registry::TranslatorRegistry,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::Serialize;
 use serde_json::json;
 
 /// One database url can be linked up to multiple schema locations.
 /// The "name" does not need to match the db name.
+/// The source decides how the url is read, for a .sql dump it's a path on disk.
 #[derive(Serialize, Clone)]
 pub struct Database {
     pub name: String,
     pub db_url: String,
+    pub source: RemoteSource,
     pub disk_mappings: Vec<DiskMapping>,
 }
 
 impl Database {
-    /// Pull the database info from the db and propagate it.
-    pub fn sync(&self) -> Result<()> {
-        let descriptions = self.get_descriptions();
+    /// Pull the database info from the db and propagate it. Returns the translators' warnings.
+    pub fn sync(&self, registry: &TranslatorRegistry) -> Result<Vec<String>> {
+        let descriptions = self.get_descriptions()?;
+        let mut warnings = vec![];
         for mapping in self.disk_mappings.iter() {
-            self.sync_one(mapping.format, mapping.path.to_owned(), &descriptions)?;
+            warnings.extend(self.sync_one(registry, mapping, &descriptions)?);
         }
-        Ok(())
+        Ok(warnings)
     }
-    /// Sync one database schema
+    /// Sync one database schema, returning anything the format couldn't represent.
     pub fn sync_one(
         &self,
-        format: AcceptedFormat,
-        path: String,
-        descriptions: &Vec<sql::Table>,
-    ) -> Result<()> {
-        match format {
-            AcceptedFormat::Json => {
-                let translator = JsonTranslator { path, json: None };
-                translator.write_to_disk(&descriptions)?;
-            }
-            AcceptedFormat::Prisma => {
-                let translator = PrismaTranslator {
-                    path,
-                    disk_schema: None,
-                    db_schema: None,
-                };
-                translator.write_to_disk(&descriptions)?;
-            }
-        }
-        Ok(())
+        registry: &TranslatorRegistry,
+        mapping: &DiskMapping,
+        descriptions: &[sql::Table],
+    ) -> Result<Vec<String>> {
+        let descriptions = mapping.table_order.ordered(descriptions);
+        let translator = self.translator(registry, mapping)?;
+        translator.write_to_disk(&descriptions)?;
+        Ok(translator.warnings(&descriptions))
+    }
+
+    /// The translator for one of the database's mappings, from the registered formats.
+    pub fn translator(
+        &self,
+        registry: &TranslatorRegistry,
+        mapping: &DiskMapping,
+    ) -> Result<Box<dyn Translator>> {
+        registry.translator(mapping.format.as_string(), mapping)
+    }
+
+    /// Read a mapping's file back into tables, without connecting to the database.
+    pub fn load_from_disk(
+        &self,
+        registry: &TranslatorRegistry,
+        mapping: &DiskMapping,
+    ) -> Result<Vec<sql::Table>> {
+        let mut translator = self.translator(registry, mapping)?;
+        translator.load_from_disk()?;
+        translator.to_tables()
+    }
+
+    /// Write the tables in one format's file out to another format's mapping, without
+    /// connecting to the database.
+    pub fn convert(
+        &self,
+        registry: &TranslatorRegistry,
+        from: AcceptedFormat,
+        to: AcceptedFormat,
+    ) -> Result<Vec<String>> {
+        let tables = self.load_from_disk(registry, self.find_disk_mapping(from)?)?;
+        self.sync_one(registry, self.find_disk_mapping(to)?, &tables)
+    }
+
+    /// The database's mapping for a format.
+    pub fn find_disk_mapping(&self, format: AcceptedFormat) -> Result<&DiskMapping> {
+        self.disk_mappings
+            .iter()
+            .find(|disk_mapping| disk_mapping.format == format)
+            .ok_or_else(|| anyhow!("{} has no {} mapping", self.name, format))
     }
 
     /// Update the name associated with your database.
@@ -56,20 +94,34 @@ impl Database {
         self.name = new_name;
     }
 
-    /// Update the database url.
+    /// Update the database url, along with the source it looks like it belongs to.
     pub fn update_db_url(&mut self, new_db_url: String) {
+        self.source = RemoteSource::from_url(&new_db_url);
         self.db_url = new_db_url;
     }
 
+    /// Update where the database's tables are read from.
+    pub fn update_source(&mut self, source: RemoteSource) {
+        self.source = source;
+    }
+
     /// Push a new disk mapping to the database. Does not save to disk.
     pub fn create_disk_mapping(&mut self, format: AcceptedFormat, path: String) {
-        let mapping = DiskMapping { format, path };
+        let mapping = DiskMapping {
+            format,
+            path,
+            naming: NamingPolicy::default(),
+            merge: false,
+            prisma: PrismaOptions::default(),
+            json_shape: JsonShape::default(),
+            table_order: TableOrder::default(),
+        };
         self.disk_mappings.push(mapping)
     }
 
     /// Map a local path to an accepted format, or create a new mapping if none exists.
     pub fn update_disk_mapping(&mut self, format: AcceptedFormat, path: String) {
-        if self.disk_mappings.len() == 0 {
+        if self.disk_mappings.is_empty() {
             self.create_disk_mapping(format, path);
             return;
         }
@@ -88,17 +140,81 @@ impl Database {
         self.disk_mappings[mapping_update_index].path = path;
     }
 
+    /// Update how the models and fields written for a format are named.
+    pub fn update_naming(&mut self, format: AcceptedFormat, naming: NamingPolicy) {
+        for disk_mapping in self.disk_mappings.iter_mut() {
+            if disk_mapping.format == format {
+                disk_mapping.naming = naming;
+            }
+        }
+    }
+
+    /// Update whether the output for a format is merged into the schema already on disk.
+    pub fn update_merge(&mut self, format: AcceptedFormat, merge: bool) {
+        for disk_mapping in self.disk_mappings.iter_mut() {
+            if disk_mapping.format == format {
+                disk_mapping.merge = merge;
+            }
+        }
+    }
+
+    /// Update the order tables are written out in for a format.
+    pub fn update_table_order(&mut self, format: AcceptedFormat, order: TableOrder) {
+        for disk_mapping in self.disk_mappings.iter_mut() {
+            if disk_mapping.format == format {
+                disk_mapping.table_order = order;
+            }
+        }
+    }
+
+    /// Update how the JSON output for a format is laid out.
+    pub fn update_json_shape(&mut self, format: AcceptedFormat, shape: JsonShape) {
+        for disk_mapping in self.disk_mappings.iter_mut() {
+            if disk_mapping.format == format {
+                disk_mapping.json_shape = shape;
+            }
+        }
+    }
+
+    /// Update the datasource and generator blocks written for a format.
+    pub fn update_prisma_options(&mut self, format: AcceptedFormat, options: PrismaOptions) {
+        for disk_mapping in self.disk_mappings.iter_mut() {
+            if disk_mapping.format == format {
+                disk_mapping.prisma = options.clone();
+            }
+        }
+    }
+
     /// Get a json value of the database.
     pub fn to_json(&self) -> serde_json::Value {
         json!({
             "name": self.name,
             "db_url": self.db_url,
+            "source": self.source,
             "disk_mappings": self.disk_mappings
         })
     }
 
+    /// The remote the database's tables are read from.
+    pub fn remote(&self) -> Box<dyn Remote> {
+        match self.source {
+            RemoteSource::MySql => Box::new(MySqlRemote {
+                url: self.db_url.clone(),
+            }),
+            RemoteSource::Ddl => Box::new(DdlRemote {
+                path: self.db_url.clone(),
+            }),
+            RemoteSource::Postgres => Box::new(PostgresRemote {
+                url: self.db_url.clone(),
+            }),
+            RemoteSource::Sqlite => Box::new(SqliteRemote {
+                url: self.db_url.clone(),
+            }),
+        }
+    }
+
     /// Get the database table descriptions from remote
-    pub fn get_descriptions(&self) -> Vec<sql::Table> {
-        sql::get_table_descriptions(&self.db_url).expect("Failed to get table descriptions")
+    pub fn get_descriptions(&self) -> Result<Vec<sql::Table>> {
+        self.remote().get_table_descriptions()
     }
 }
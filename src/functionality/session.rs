@@ -1,13 +1,10 @@
 use crate::flags::flag_parser::CommandLineFlags;
 use crate::functionality::{
     database,
-    structure::{AcceptedFormat, DiskMapping},
+    structure::{AcceptedFormat, DiskMapping, RemoteSource},
 };
-use crate::translators::{
-    behaviour::TranslatorBehaviour, json_translator::JsonTranslator,
-    prisma_translator::PrismaTranslator,
-};
-use anyhow::Result;
+use crate::translators::registry::TranslatorRegistry;
+use anyhow::{bail, Result};
 use serde_json;
 use std::fs::File;
 use std::io::BufWriter;
@@ -18,6 +15,8 @@ pub struct Session {
     pub databases: Vec<database::Database>,
     pub _data_location: String,
     pub command_line_flags: Option<CommandLineFlags>,
+    /// The translators mappings are written with, register one here to map a new format.
+    pub registry: TranslatorRegistry,
 }
 
 impl Session {
@@ -28,12 +27,13 @@ impl Session {
             databases: Vec::new(),
             _data_location: full_path.to_string(),
             command_line_flags: None,
+            registry: TranslatorRegistry::default(),
         };
         s.load().ok()?;
-        if s.databases.len() == 0 {
+        if s.databases.is_empty() {
             return None;
         }
-        return Some(s);
+        Some(s)
     }
 
     /// Create a new session with no databases.
@@ -42,6 +42,7 @@ impl Session {
             databases: Vec::new(),
             _data_location: full_path.to_string(),
             command_line_flags: None,
+            registry: TranslatorRegistry::default(),
         })
     }
 
@@ -52,18 +53,21 @@ impl Session {
     /// Load an existing session from the disk.
     pub fn load(&mut self) -> Result<()> {
         let path = Path::new(self._data_location.as_str());
-        let file = File::open(&path).unwrap_or_else(|_| File::create(&path).unwrap());
+        let file = File::open(path).unwrap_or_else(|_| File::create(path).unwrap());
         let reader = std::io::BufReader::new(file);
         let the_json: Vec<serde_json::Value> =
             serde_json::from_reader(reader).unwrap_or_else(|_| Vec::new());
         for mut database in the_json {
             let name = database["name"].as_str().unwrap().to_string();
             let db_url = database["db_url"].as_str().unwrap().to_string();
+            let source = serde_json::from_value(database["source"].take())
+                .unwrap_or_else(|_| RemoteSource::from_url(&db_url));
             let disk_mappings = DiskMapping::from_json(database["disk_mappings"].take())
                 .expect("disk mappings to parse successfully from json");
             let database = database::Database {
                 name,
                 db_url,
+                source,
                 disk_mappings,
             };
             self.add_database(database)
@@ -85,22 +89,58 @@ impl Session {
         Ok(())
     }
 
-    pub fn sync(&self) -> Result<()> {
+    /// Sync every database, returning the translators' warnings.
+    pub fn sync(&self) -> Result<Vec<String>> {
+        let mut warnings = vec![];
         for database in &self.databases {
-            database.sync()?;
+            warnings.extend(database.sync(&self.registry)?);
         }
-        Ok(())
+        Ok(warnings)
+    }
+
+    /// Convert one format's file into another's for a database, without connecting to it.
+    pub fn convert(
+        &self,
+        db_index: usize,
+        from: AcceptedFormat,
+        to: AcceptedFormat,
+    ) -> Result<Vec<String>> {
+        self.databases[db_index].convert(&self.registry, from, to)
+    }
+
+    /// Run the conversion asked for on the command line, if any, returning its warnings.
+    pub fn convert_from_flags(&self) -> Result<Option<Vec<String>>> {
+        let Some(flags) = &self.command_line_flags else {
+            return Ok(None);
+        };
+        let Some(formats) = &flags.convert else {
+            return Ok(None);
+        };
+        let format = |format: &String| match self.registry.format(format) {
+            Some(format) => Ok(format),
+            None => bail!(
+                "unknown format {}, expected one of {}",
+                format,
+                self.registry.formats().join(", ")
+            ),
+        };
+        let (from, to) = (format(&formats[0])?, format(&formats[1])?);
+        let db_index = match &flags.database {
+            Some(name) => match self.databases.iter().position(|db| &db.name == name) {
+                Some(db_index) => db_index,
+                None => bail!("no database is named {}", name),
+            },
+            None if self.databases.is_empty() => bail!("there are no databases in the session"),
+            None if self.databases.len() == 1 => 0,
+            None => bail!("pick the database to convert with --database"),
+        };
+        Ok(Some(self.convert(db_index, from, to)?))
     }
 
     fn find_existing_database_index(&self, db_url: &str) -> Option<usize> {
-        let mut index = 0;
-        for database in &self.databases {
-            if database.db_url == db_url {
-                return Some(index);
-            }
-            index += 1;
-        }
-        None
+        self.databases
+            .iter()
+            .position(|database| database.db_url == db_url)
     }
 
     pub fn sort(&mut self) {
@@ -111,7 +151,7 @@ impl Session {
     /// This does not write the schemas themselves, instead it saves the database names and urls.
     pub fn save_database_info(&self) -> Result<(), serde_json::Error> {
         let path = Path::new(self._data_location.as_str());
-        let file = File::create(&path).unwrap();
+        let file = File::create(path).unwrap();
         let buf_writer = BufWriter::new(file);
         let the_json = self
             .databases
@@ -122,105 +162,69 @@ impl Session {
         Ok(())
     }
 
+    /// The selected database's mapping for the selected format.
+    fn selected_disk_mapping(
+        &self,
+        selection: usize,
+        db_index: usize,
+        options: &[String],
+    ) -> Result<&DiskMapping> {
+        match options
+            .get(selection)
+            .and_then(|format| self.registry.format(format))
+        {
+            Some(format) => self.databases[db_index].find_disk_mapping(format),
+            None => bail!("invalid selection"),
+        }
+    }
+
     pub fn get_current_local_database(
         &self,
         selection: usize,
         db_index: usize,
-        options: &Vec<String>,
+        options: &[String],
     ) -> Result<String> {
-        match options[selection].as_str() {
-            "json" => {
-                let schema_path_str = self.databases[db_index].disk_mappings[selection]
-                    .path
-                    .clone();
-                let mut translator = JsonTranslator {
-                    path: schema_path_str,
-                    json: None,
-                };
-                translator.load_from_disk()?;
-                let resp_str = translator.get_string();
-                Ok(resp_str)
-            }
-            "prisma" => {
-                let mut translator = PrismaTranslator {
-                    path: self.databases[db_index].disk_mappings[selection]
-                        .path
-                        .clone(),
-                    disk_schema: None,
-                    db_schema: None,
-                };
-                translator.load_from_disk()?;
-                Ok(translator.get_string())
+        let database = &self.databases[db_index];
+        let mapping = self.selected_disk_mapping(selection, db_index, options)?;
+        let mut translator = database.translator(&self.registry, mapping)?;
+        translator.load_from_disk()?;
+        // The database may not be reachable, in which case the disk schema is shown with why.
+        if translator.compares_with_database() {
+            match database.remote().get_table_descriptions() {
+                Ok(tables) => translator.load_from_database(&mapping.table_order.ordered(&tables)),
+                Err(error) => {
+                    return Ok(format!(
+                        "{}couldn't read the database to compare with: {:#}\n",
+                        translator.get_string(),
+                        error
+                    ))
+                }
             }
-            _ => anyhow::bail!("invalid selection"),
         }
+        Ok(translator.get_string())
     }
 
     pub fn view_table_from_database(
         &self,
         selection: usize,
         db_index: usize,
-        options: &Vec<String>,
+        options: &[String],
     ) -> Result<String> {
-        match options[selection].as_str() {
-            "json" => {
-                let mut translator = JsonTranslator {
-                    path: self.databases[db_index].disk_mappings[selection]
-                        .path
-                        .clone(),
-                    json: None,
-                };
-                translator.load_from_database(&self.databases[db_index].get_descriptions());
-                Ok(translator.get_string())
-            }
-            "prisma" => {
-                let mut translator = PrismaTranslator {
-                    path: self.databases[db_index].disk_mappings[selection]
-                        .path
-                        .clone(),
-                    disk_schema: None,
-                    db_schema: None,
-                };
-                translator.load_from_database(&self.databases[db_index].get_descriptions());
-                Ok(translator.get_string())
-            }
-            _ => {
-                anyhow::bail!("invalid selection")
-            }
-        }
+        let database = &self.databases[db_index];
+        let mapping = self.selected_disk_mapping(selection, db_index, options)?;
+        let mut translator = database.translator(&self.registry, mapping)?;
+        translator.load_from_database(&mapping.table_order.ordered(&database.get_descriptions()?));
+        Ok(translator.get_string())
     }
 
     pub fn write_one_schema_from_database(
         &self,
         selection: usize,
         db_index: usize,
-        options: &Vec<String>,
-    ) -> Result<()> {
-        let descriptions = self.databases[db_index].get_descriptions();
-        match options[selection].as_str() {
-            "json" => {
-                self.databases[db_index].sync_one(
-                    AcceptedFormat::Json,
-                    self.databases[db_index].disk_mappings[selection]
-                        .path
-                        .clone(),
-                    &descriptions,
-                )?;
-                Ok(())
-            }
-            "prisma" => {
-                self.databases[db_index].sync_one(
-                    AcceptedFormat::Prisma,
-                    self.databases[db_index].disk_mappings[selection]
-                        .path
-                        .clone(),
-                    &descriptions,
-                )?;
-                Ok(())
-            }
-            _ => {
-                anyhow::bail!("invalid selection")
-            }
-        }
+        options: &[String],
+    ) -> Result<Vec<String>> {
+        let database = &self.databases[db_index];
+        let mapping = self.selected_disk_mapping(selection, db_index, options)?;
+        database.sync_one(&self.registry, mapping, &database.get_descriptions()?)
     }
 }
@@ -1,7 +1,10 @@
+use crate::translators::json::shape::JsonShape;
+use crate::translators::ordering::TableOrder;
+use crate::translators::prisma::{naming::NamingPolicy, options::PrismaOptions};
 use anyhow::Result;
 use core::fmt::{self, Display};
 use dotenvy::dotenv;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json;
 use std::env;
 
@@ -22,6 +25,21 @@ pub struct DiskMapping {
     pub format: AcceptedFormat,
     // #[serde(borrow)]
     pub path: String,
+    /// How Prisma models and fields are named, ignored by the other formats.
+    #[serde(default)]
+    pub naming: NamingPolicy,
+    /// Whether Prisma output is merged into the schema at the path, keeping its hand edits.
+    #[serde(default)]
+    pub merge: bool,
+    /// The datasource and generator blocks written for Prisma, ignored by the other formats.
+    #[serde(default)]
+    pub prisma: PrismaOptions,
+    /// How JSON output is laid out, ignored by the other formats.
+    #[serde(default)]
+    pub json_shape: JsonShape,
+    /// The order tables are written out in.
+    #[serde(default)]
+    pub table_order: TableOrder,
 }
 
 impl DiskMapping {
@@ -31,6 +49,16 @@ impl DiskMapping {
         struct TempMapping {
             format: AcceptedFormat,
             path: String,
+            #[serde(default)]
+            naming: NamingPolicy,
+            #[serde(default)]
+            merge: bool,
+            #[serde(default)]
+            prisma: PrismaOptions,
+            #[serde(default)]
+            json_shape: JsonShape,
+            #[serde(default)]
+            table_order: TableOrder,
         }
 
         let temp_mappings: Vec<TempMapping> = serde_json::from_value(json)?;
@@ -39,6 +67,11 @@ impl DiskMapping {
             .map(|temp_mapping| DiskMapping {
                 format: temp_mapping.format,
                 path: temp_mapping.path,
+                naming: temp_mapping.naming,
+                merge: temp_mapping.merge,
+                prisma: temp_mapping.prisma,
+                json_shape: temp_mapping.json_shape,
+                table_order: temp_mapping.table_order,
             })
             .collect();
 
@@ -46,44 +79,112 @@ impl DiskMapping {
     }
 }
 
-#[derive(Serialize, PartialEq, Deserialize, Copy, Clone, Debug)]
+/// A mapping's format, by the name its translator is registered under. Whether a translator is
+/// registered for it is only checked when the translator is looked up.
+#[derive(PartialEq, Eq, Clone, Debug)]
 pub enum AcceptedFormat {
     Json,
     Prisma,
+    /// A format registered outside mysql translate, by its name.
+    Other(String),
 }
 
 impl AcceptedFormat {
-    pub fn from_string(format: &str) -> Option<AcceptedFormat> {
-        match format {
-            "json" => Some(AcceptedFormat::Json),
-            "prisma" => Some(AcceptedFormat::Prisma),
-            _ => None,
+    /// The format with the name. Older sessions name formats as `Json` and `Prisma`, so the
+    /// built in names are read in any case.
+    pub fn from_name(format: &str) -> AcceptedFormat {
+        match format.to_lowercase().as_str() {
+            "json" => AcceptedFormat::Json,
+            "prisma" => AcceptedFormat::Prisma,
+            _ => AcceptedFormat::Other(format.to_string()),
         }
     }
-    pub fn as_string(&self) -> &'static str {
+    pub fn as_string(&self) -> &str {
         match self {
             Self::Json => "json",
             Self::Prisma => "prisma",
+            Self::Other(format) => format,
         }
     }
-    pub fn all_as_array() -> Vec<AcceptedFormat> {
-        vec![AcceptedFormat::Json, AcceptedFormat::Prisma]
+}
+
+impl Display for AcceptedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.as_string())
     }
-    pub fn all_as_string_array() -> Vec<String> {
-        AcceptedFormat::all_as_array()
-            .into_iter()
-            .map(|format| format.as_string().to_string())
-            .collect()
+}
+
+impl Serialize for AcceptedFormat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for AcceptedFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(AcceptedFormat::from_name(&String::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+/// Where a database's tables are read from.
+#[derive(Serialize, PartialEq, Deserialize, Copy, Clone, Debug)]
+pub enum RemoteSource {
+    MySql,
+    Ddl,
+    Postgres,
+    Sqlite,
+}
+
+impl RemoteSource {
+    pub fn from_string(source: &str) -> Option<RemoteSource> {
+        match source {
+            "mysql" => Some(RemoteSource::MySql),
+            "ddl" => Some(RemoteSource::Ddl),
+            "postgres" => Some(RemoteSource::Postgres),
+            "sqlite" => Some(RemoteSource::Sqlite),
+            _ => None,
+        }
+    }
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Self::MySql => "mysql",
+            Self::Ddl => "ddl",
+            Self::Postgres => "postgres",
+            Self::Sqlite => "sqlite",
+        }
+    }
+    /// Guess the source from the shape of the url, for databases saved without one.
+    pub fn from_url(url: &str) -> RemoteSource {
+        if url.ends_with(".sql") {
+            return RemoteSource::Ddl;
+        }
+        if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            return RemoteSource::Postgres;
+        }
+        if url.starts_with("sqlite://") || url.ends_with(".db") {
+            return RemoteSource::Sqlite;
+        }
+        RemoteSource::MySql
+    }
+    pub fn all_as_array() -> Vec<RemoteSource> {
+        vec![
+            RemoteSource::MySql,
+            RemoteSource::Ddl,
+            RemoteSource::Postgres,
+            RemoteSource::Sqlite,
+        ]
     }
     pub fn all_as_str_array() -> Vec<&'static str> {
-        AcceptedFormat::all_as_array()
+        RemoteSource::all_as_array()
             .into_iter()
-            .map(|format| format.as_string())
+            .map(|source| source.as_string())
             .collect()
     }
 }
 
-impl Display for AcceptedFormat {
+impl Display for RemoteSource {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self.as_string())
     }
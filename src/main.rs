@@ -16,8 +16,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ),
     };
     session.borrow_mut().set_command_line_flags(args);
+    if let Some(warnings) = session.borrow().convert_from_flags()? {
+        for warning in warnings {
+            eprintln!("warning: {}", warning);
+        }
+        return Ok(());
+    }
     let mut ui = TerminalUI::new(session);
-    if ui.session.borrow().databases.len() == 0 {
+    if ui.session.borrow().databases.is_empty() {
         ui.create_database_entry()?;
     }
     ui.main_loop()?;
@@ -0,0 +1,8 @@
+use crate::remotes::sql::Table;
+use anyhow::Result;
+
+/// A trait for a source of table descriptions, such as a live database or a dump on disk.
+pub trait Remote {
+    /// Reads every table from the source.
+    fn get_table_descriptions(&self) -> Result<Vec<Table>>;
+}
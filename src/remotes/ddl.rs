@@ -0,0 +1,820 @@
+use crate::remotes::behaviour::Remote;
+use crate::remotes::sql::{Description, Dialect, ForeignKeyInformation, Table, TableKeys};
+use anyhow::{anyhow, bail, Result};
+use std::fs;
+
+/// A piece of a SQL dump.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Keywords, unquoted names and numbers.
+    Word(String),
+    /// A `backtick quoted` name.
+    Identifier(String),
+    /// A 'single quoted' string, with the escapes resolved.
+    Str(String),
+    Symbol(char),
+}
+
+impl Token {
+    /// The token as it would be written back out in SQL.
+    fn as_sql(&self) -> String {
+        match self {
+            Token::Word(word) => word.clone(),
+            Token::Identifier(identifier) => format!("`{}`", identifier),
+            Token::Str(string) => format!("'{}'", string.replace('\'', "''")),
+            Token::Symbol(symbol) => symbol.to_string(),
+        }
+    }
+}
+
+/// Splits a SQL dump into tokens, dropping whitespace and comments. The contents of versioned
+/// comments (`/*!80023 INVISIBLE */`) are read as SQL, the same as MySQL does.
+fn tokenize(sql: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens: Vec<Token> = vec![];
+    let mut in_versioned_comment = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') && chars.get(i + 2) == Some(&'!') {
+            i += 3;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            in_versioned_comment = true;
+        } else if in_versioned_comment && c == '*' && chars.get(i + 1) == Some(&'/') {
+            i += 2;
+            in_versioned_comment = false;
+        } else if c == '#' || (c == '-' && chars.get(i + 1) == Some(&'-')) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '`' {
+            let mut identifier = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => bail!("unterminated identifier `{}", identifier),
+                    Some('`') if chars.get(i + 1) == Some(&'`') => {
+                        identifier.push('`');
+                        i += 2;
+                    }
+                    Some('`') => {
+                        i += 1;
+                        break;
+                    }
+                    Some(x) => {
+                        identifier.push(*x);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push(Token::Identifier(identifier));
+        } else if c == '\'' || c == '"' {
+            let (string, end) = read_string(&chars, i)?;
+            tokens.push(Token::Str(string));
+            i = end;
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || ['_', '$', '.'].contains(&chars[i]))
+            {
+                i += 1;
+            }
+            let mut word: String = chars[start..i].iter().collect();
+            // Bit and hex literals such as b'0101' are kept whole.
+            if ["b", "B", "x", "X"].contains(&word.as_str()) && chars.get(i) == Some(&'\'') {
+                let (string, end) = read_string(&chars, i)?;
+                word = format!("{}'{}'", word.to_lowercase(), string);
+                i = end;
+            }
+            tokens.push(Token::Word(word));
+        } else {
+            tokens.push(Token::Symbol(c));
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+/// Reads a quoted string starting at `start`, returning it and the index after its closing quote.
+fn read_string(chars: &[char], start: usize) -> Result<(String, usize)> {
+    let quote = chars[start];
+    let mut string = String::new();
+    let mut i = start + 1;
+    loop {
+        match chars.get(i) {
+            None => bail!("unterminated string {}{}", quote, string),
+            Some('\\') => {
+                match chars.get(i + 1) {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some('r') => string.push('\r'),
+                    Some('0') => string.push('\0'),
+                    Some(x) => string.push(*x),
+                    None => bail!("unterminated string {}{}", quote, string),
+                }
+                i += 2;
+            }
+            Some(x) if *x == quote && chars.get(i + 1) == Some(&quote) => {
+                string.push(quote);
+                i += 2;
+            }
+            Some(x) if *x == quote => return Ok((string, i + 1)),
+            Some(x) => {
+                string.push(*x);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Joins tokens back into SQL, without spaces around brackets or commas.
+fn tokens_as_sql(tokens: &[Token]) -> String {
+    let mut sql = String::new();
+    let mut previous: Option<&Token> = None;
+    for token in tokens {
+        let tight = matches!(previous, None | Some(Token::Symbol('(' | ',')))
+            || matches!(
+                token,
+                Token::Symbol('(') | Token::Symbol(')') | Token::Symbol(',')
+            );
+        if !tight {
+            sql.push(' ');
+        }
+        sql.push_str(&token.as_sql());
+        previous = Some(token);
+    }
+    sql
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Parser {
+        Parser {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek_keyword(keyword) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn eat_keywords(&mut self, keywords: &[&str]) -> bool {
+        let start = self.position;
+        for keyword in keywords {
+            if !self.eat_keyword(keyword) {
+                self.position = start;
+                return false;
+            }
+        }
+        true
+    }
+
+    fn eat_symbol(&mut self, symbol: char) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<()> {
+        match self.next() {
+            Some(Token::Symbol(x)) if x == symbol => Ok(()),
+            other => Err(anyhow!("expected '{}', found {:?}", symbol, other)),
+        }
+    }
+
+    /// A name, either quoted or bare. Qualified names give back the last part.
+    fn identifier(&mut self) -> Result<String> {
+        let mut name = match self.next() {
+            Some(Token::Identifier(name)) => name,
+            Some(Token::Word(word)) => word.rsplit('.').next().unwrap_or(&word).to_string(),
+            other => bail!("expected a name, found {:?}", other),
+        };
+        while self.eat_symbol('.') {
+            name = self.identifier()?;
+        }
+        Ok(name)
+    }
+
+    /// The tokens between an opening bracket (which must be next) and its matching close.
+    fn bracketed(&mut self) -> Result<Vec<Token>> {
+        self.expect_symbol('(')?;
+        let mut depth = 1;
+        let mut inner: Vec<Token> = vec![];
+        loop {
+            match self.next() {
+                None => bail!("unclosed bracket"),
+                Some(Token::Symbol('(')) => depth += 1,
+                Some(Token::Symbol(')')) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(inner);
+                    }
+                }
+                _ => {}
+            }
+            inner.push(self.tokens[self.position - 1].clone());
+        }
+    }
+
+    /// Moves past the end of the current statement.
+    fn skip_statement(&mut self) {
+        while let Some(token) = self.next() {
+            if token == Token::Symbol(';') {
+                return;
+            }
+        }
+    }
+}
+
+/// Splits tokens on the commas which aren't inside of brackets.
+fn split_on_commas(tokens: Vec<Token>) -> Vec<Vec<Token>> {
+    let mut pieces: Vec<Vec<Token>> = vec![vec![]];
+    let mut depth = 0;
+    for token in tokens {
+        match token {
+            Token::Symbol('(') => depth += 1,
+            Token::Symbol(')') => depth -= 1,
+            Token::Symbol(',') if depth == 0 => {
+                pieces.push(vec![]);
+                continue;
+            }
+            _ => {}
+        }
+        pieces.last_mut().expect("a piece to push to").push(token);
+    }
+    pieces.retain(|piece| !piece.is_empty());
+    pieces
+}
+
+/// The column names of an index's key parts. Prefix lengths and sort orders are dropped, and
+/// functional key parts have no column.
+fn key_part_names(tokens: Vec<Token>) -> Vec<Option<String>> {
+    split_on_commas(tokens)
+        .into_iter()
+        .map(|part| match part.first() {
+            Some(Token::Identifier(name)) => Some(name.clone()),
+            Some(Token::Word(name)) => Some(name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// An index declared in a table definition.
+struct IndexDefinition {
+    name: String,
+    columns: Vec<Option<String>>,
+    is_unique: bool,
+}
+
+struct ForeignKeyDefinition {
+    name: String,
+    columns: Vec<String>,
+    referenced_table: String,
+    referenced_columns: Vec<String>,
+    on_delete: String,
+    on_update: String,
+}
+
+struct ColumnDefinition {
+    name: String,
+    type_: String,
+    nullable: bool,
+    default: Option<String>,
+    extra: Vec<String>,
+    comment: Option<String>,
+}
+
+/// Everything in one CREATE TABLE statement.
+struct TableDefinition {
+    name: String,
+    comment: Option<String>,
+    columns: Vec<ColumnDefinition>,
+    primary_key: Vec<String>,
+    indexes: Vec<IndexDefinition>,
+    foreign_keys: Vec<ForeignKeyDefinition>,
+}
+
+impl TableDefinition {
+    /// Picks a name for an unnamed index the same way MySQL does, after its first column.
+    fn index_name(&self, columns: &[Option<String>]) -> String {
+        let base = columns
+            .iter()
+            .flatten()
+            .next()
+            .cloned()
+            .unwrap_or_else(|| String::from("functional_index"));
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while self.indexes.iter().any(|index| index.name == name) {
+            name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        name
+    }
+
+    fn parse_definition(&mut self, tokens: Vec<Token>) -> Result<()> {
+        let mut parser = Parser::new(tokens);
+        let mut constraint_name: Option<String> = None;
+        if parser.eat_keyword("CONSTRAINT")
+            && !parser.peek_keyword("PRIMARY")
+            && !parser.peek_keyword("UNIQUE")
+            && !parser.peek_keyword("FOREIGN")
+            && !parser.peek_keyword("CHECK")
+        {
+            constraint_name = Some(parser.identifier()?);
+        }
+        if parser.eat_keywords(&["PRIMARY", "KEY"]) {
+            skip_index_type(&mut parser);
+            self.primary_key = key_part_names(parser.bracketed()?)
+                .into_iter()
+                .flatten()
+                .collect();
+        } else if parser.peek_keyword("UNIQUE")
+            || parser.peek_keyword("KEY")
+            || parser.peek_keyword("INDEX")
+            || parser.peek_keyword("FULLTEXT")
+            || parser.peek_keyword("SPATIAL")
+        {
+            let is_unique = parser.peek_keyword("UNIQUE");
+            parser.next();
+            let _ = parser.eat_keyword("KEY") || parser.eat_keyword("INDEX");
+            let mut name = constraint_name;
+            if parser.peek() != Some(&Token::Symbol('(')) && !parser.peek_keyword("USING") {
+                name = Some(parser.identifier()?);
+            }
+            skip_index_type(&mut parser);
+            let columns = key_part_names(parser.bracketed()?);
+            let name = name.unwrap_or_else(|| self.index_name(&columns));
+            self.indexes.push(IndexDefinition {
+                name,
+                columns,
+                is_unique,
+            });
+        } else if parser.eat_keywords(&["FOREIGN", "KEY"]) {
+            if parser.peek() != Some(&Token::Symbol('(')) {
+                let index_name = parser.identifier()?;
+                constraint_name = constraint_name.or(Some(index_name));
+            }
+            let columns: Vec<String> = key_part_names(parser.bracketed()?)
+                .into_iter()
+                .flatten()
+                .collect();
+            if !parser.eat_keyword("REFERENCES") {
+                bail!("expected REFERENCES in foreign key on {}", self.name);
+            }
+            let referenced_table = parser.identifier()?;
+            let referenced_columns: Vec<String> = key_part_names(parser.bracketed()?)
+                .into_iter()
+                .flatten()
+                .collect();
+            let mut on_delete = String::from("NO ACTION");
+            let mut on_update = String::from("NO ACTION");
+            while !parser.is_done() {
+                if parser.eat_keywords(&["ON", "DELETE"]) {
+                    on_delete = referential_action(&mut parser)?;
+                } else if parser.eat_keywords(&["ON", "UPDATE"]) {
+                    on_update = referential_action(&mut parser)?;
+                } else {
+                    parser.next();
+                }
+            }
+            let name = constraint_name
+                .unwrap_or_else(|| format!("{}_ibfk_{}", self.name, self.foreign_keys.len() + 1));
+            self.foreign_keys.push(ForeignKeyDefinition {
+                name,
+                columns,
+                referenced_table,
+                referenced_columns,
+                on_delete,
+                on_update,
+            });
+        } else if parser.peek_keyword("CHECK") {
+            // Check constraints don't make it into any of the translations.
+        } else {
+            self.parse_column(&mut parser)?;
+        }
+        Ok(())
+    }
+
+    fn parse_column(&mut self, parser: &mut Parser) -> Result<()> {
+        let name = parser.identifier()?;
+        let mut type_ = match parser.next() {
+            Some(Token::Word(word)) => word.to_lowercase(),
+            other => bail!(
+                "expected a type for {}.{}, found {:?}",
+                self.name,
+                name,
+                other
+            ),
+        };
+        let _ = parser.eat_keyword("PRECISION") || parser.eat_keyword("VARYING");
+        if parser.peek() == Some(&Token::Symbol('(')) {
+            let arguments = parser.bracketed()?;
+            type_.push_str(&format!("({})", tokens_as_sql(&arguments)));
+        }
+        loop {
+            if parser.eat_keyword("UNSIGNED") {
+                type_.push_str(" unsigned");
+            } else if parser.eat_keyword("ZEROFILL") {
+                type_.push_str(" zerofill");
+            } else if !parser.eat_keyword("SIGNED") {
+                break;
+            }
+        }
+        let mut column = ColumnDefinition {
+            name,
+            type_,
+            nullable: true,
+            default: None,
+            extra: vec![],
+            comment: None,
+        };
+        let mut on_update: Option<String> = None;
+        let mut generated: Option<&str> = None;
+        while !parser.is_done() {
+            if parser.eat_keywords(&["NOT", "NULL"]) {
+                column.nullable = false;
+            } else if parser.eat_keyword("NULL") {
+                column.nullable = true;
+            } else if parser.eat_keyword("DEFAULT") {
+                let (default, is_generated) = parse_default(parser)?;
+                column.default = default;
+                if is_generated {
+                    column.extra.push(String::from("DEFAULT_GENERATED"));
+                }
+            } else if parser.eat_keyword("AUTO_INCREMENT") {
+                column.extra.push(String::from("auto_increment"));
+            } else if parser.eat_keywords(&["ON", "UPDATE"]) {
+                let (value, _) = parse_default(parser)?;
+                on_update = value;
+            } else if parser.eat_keywords(&["PRIMARY", "KEY"]) || parser.eat_keyword("KEY") {
+                self.primary_key = vec![column.name.clone()];
+            } else if parser.eat_keyword("UNIQUE") {
+                let _ = parser.eat_keyword("KEY");
+                let columns = vec![Some(column.name.clone())];
+                self.indexes.push(IndexDefinition {
+                    name: self.index_name(&columns),
+                    columns,
+                    is_unique: true,
+                });
+            } else if parser.eat_keywords(&["GENERATED", "ALWAYS"]) || parser.eat_keyword("AS") {
+                let _ = parser.eat_keyword("AS");
+                parser.bracketed()?;
+                generated = Some("VIRTUAL");
+            } else if parser.eat_keyword("STORED") {
+                generated = Some("STORED");
+            } else if parser.eat_keyword("VIRTUAL") {
+                generated = Some("VIRTUAL");
+            } else if parser.eat_keyword("INVISIBLE") {
+                column.extra.push(String::from("INVISIBLE"));
+            } else if parser.eat_keyword("COMMENT") {
+                column.comment = match parser.next() {
+                    Some(Token::Str(comment)) => Some(comment),
+                    other => bail!("expected a comment for {}, found {:?}", column.name, other),
+                };
+            } else if parser.eat_keyword("COLLATE")
+                || parser.eat_keywords(&["CHARACTER", "SET"])
+                || parser.eat_keyword("CHARSET")
+                || parser.eat_keyword("COLUMN_FORMAT")
+                || parser.eat_keyword("STORAGE")
+                || parser.eat_keyword("SRID")
+            {
+                parser.next();
+            } else if parser.peek_keyword("CHECK") {
+                parser.next();
+                parser.bracketed()?;
+            } else if parser.peek_keyword("REFERENCES") {
+                // Inline references are parsed but ignored by MySQL, so they are here too.
+                break;
+            } else {
+                parser.next();
+            }
+        }
+        if let Some(on_update) = on_update {
+            column.extra.push(format!("on update {}", on_update));
+        }
+        if let Some(generated) = generated {
+            column.extra.push(format!("{} GENERATED", generated));
+        }
+        self.columns.push(column);
+        Ok(())
+    }
+
+    /// The Key column `DESCRIBE` would show for a column.
+    fn column_key(&self, column_name: &str) -> &'static str {
+        if self.primary_key.iter().any(|name| name == column_name) {
+            return "PRI";
+        }
+        let first_in = |index: &&IndexDefinition| {
+            index.columns.first() == Some(&Some(column_name.to_string()))
+        };
+        if self
+            .indexes
+            .iter()
+            .filter(first_in)
+            .any(|index| index.is_unique && index.columns.len() == 1)
+        {
+            return "UNI";
+        }
+        if self.indexes.iter().any(|index| first_in(&index)) {
+            return "MUL";
+        }
+        ""
+    }
+
+    /// The key rows information_schema would give back for this table.
+    fn key_information(&self) -> Vec<ForeignKeyInformation> {
+        let mut rows: Vec<ForeignKeyInformation> = vec![];
+        let empty_row = ForeignKeyInformation {
+            constraint_name: None,
+            constraint_type: None,
+            column_name: None,
+            ordinal_position: None,
+            referenced_table_name: None,
+            referenced_column_name: None,
+            index_name: None,
+            seq_in_index: None,
+            cardinality: None,
+            index_type: None,
+            update_rule: None,
+            delete_rule: None,
+        };
+        let mut indexes: Vec<(&str, Vec<Option<String>>)> = vec![];
+        if !self.primary_key.is_empty() {
+            indexes.push((
+                "PRIMARY",
+                self.primary_key.iter().cloned().map(Some).collect(),
+            ));
+        }
+        for index in self.indexes.iter() {
+            indexes.push((&index.name, index.columns.clone()));
+            if !index.is_unique {
+                continue;
+            }
+            for (i, column_name) in index.columns.iter().enumerate() {
+                rows.push(ForeignKeyInformation {
+                    constraint_name: Some(index.name.clone()),
+                    constraint_type: Some(String::from("UNIQUE")),
+                    column_name: column_name.clone(),
+                    ordinal_position: Some(i + 1),
+                    ..empty_row.clone()
+                });
+            }
+        }
+        for (index_name, columns) in indexes {
+            for (i, column_name) in columns.into_iter().enumerate() {
+                rows.push(ForeignKeyInformation {
+                    column_name,
+                    ordinal_position: Some(i + 1),
+                    index_name: Some(index_name.to_string()),
+                    seq_in_index: Some(i + 1),
+                    index_type: Some(String::from("BTREE")),
+                    ..empty_row.clone()
+                });
+            }
+        }
+        for foreign_key in self.foreign_keys.iter() {
+            for (i, (column_name, referenced_column_name)) in foreign_key
+                .columns
+                .iter()
+                .zip(foreign_key.referenced_columns.iter())
+                .enumerate()
+            {
+                rows.push(ForeignKeyInformation {
+                    constraint_name: Some(foreign_key.name.clone()),
+                    constraint_type: Some(String::from("FOREIGN KEY")),
+                    column_name: Some(column_name.clone()),
+                    ordinal_position: Some(i + 1),
+                    referenced_table_name: Some(foreign_key.referenced_table.clone()),
+                    referenced_column_name: Some(referenced_column_name.clone()),
+                    update_rule: Some(foreign_key.on_update.clone()),
+                    delete_rule: Some(foreign_key.on_delete.clone()),
+                    ..empty_row.clone()
+                });
+            }
+        }
+        rows.sort_by(|a, b| {
+            (&a.constraint_name, a.ordinal_position).cmp(&(&b.constraint_name, b.ordinal_position))
+        });
+        rows
+    }
+}
+
+impl From<TableDefinition> for Table {
+    fn from(definition: TableDefinition) -> Self {
+        let description = definition
+            .columns
+            .iter()
+            .map(|column| {
+                let is_primary = definition.primary_key.contains(&column.name);
+                Description::from((
+                    column.name.clone(),
+                    column.type_.clone(),
+                    String::from(match column.nullable && !is_primary {
+                        true => "YES",
+                        false => "NO",
+                    }),
+                    definition.column_key(&column.name).to_string(),
+                    column.default.clone(),
+                    column.extra.join(" "),
+                ))
+                .with_comment(column.comment.clone())
+            })
+            .collect();
+        Table {
+            keys: TableKeys::from(definition.key_information()),
+            name: definition.name,
+            dialect: Dialect::MySql,
+            description,
+            comment: definition.comment.filter(|comment| !comment.is_empty()),
+        }
+    }
+}
+
+fn skip_index_type(parser: &mut Parser) {
+    if parser.eat_keyword("USING") {
+        parser.next();
+    }
+}
+
+fn referential_action(parser: &mut Parser) -> Result<String> {
+    for action in [
+        vec!["CASCADE"],
+        vec!["SET", "NULL"],
+        vec!["SET", "DEFAULT"],
+        vec!["RESTRICT"],
+        vec!["NO", "ACTION"],
+    ] {
+        if parser.eat_keywords(&action) {
+            return Ok(action.join(" "));
+        }
+    }
+    bail!("unknown referential action {:?}", parser.peek())
+}
+
+/// Reads a default value the way `DESCRIBE` shows it, along with whether MySQL considers it
+/// generated (timestamps and expressions).
+fn parse_default(parser: &mut Parser) -> Result<(Option<String>, bool)> {
+    match parser.next() {
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("NULL") => Ok((None, false)),
+        Some(Token::Word(word))
+            if ["CURRENT_TIMESTAMP", "NOW", "LOCALTIME", "LOCALTIMESTAMP"]
+                .iter()
+                .any(|keyword| word.eq_ignore_ascii_case(keyword)) =>
+        {
+            let mut value = String::from("CURRENT_TIMESTAMP");
+            if parser.peek() == Some(&Token::Symbol('(')) {
+                let precision = parser.bracketed()?;
+                if !precision.is_empty() {
+                    value.push_str(&format!("({})", tokens_as_sql(&precision)));
+                }
+            }
+            Ok((Some(value), true))
+        }
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("TRUE") => {
+            Ok((Some(String::from("1")), false))
+        }
+        Some(Token::Word(word)) if word.eq_ignore_ascii_case("FALSE") => {
+            Ok((Some(String::from("0")), false))
+        }
+        Some(Token::Word(word)) => Ok((Some(word), false)),
+        Some(Token::Str(string)) => Ok((Some(string), false)),
+        Some(Token::Symbol(sign)) if sign == '-' || sign == '+' => match parser.next() {
+            Some(Token::Word(number)) if sign == '-' => Ok((Some(format!("-{}", number)), false)),
+            Some(Token::Word(number)) => Ok((Some(number), false)),
+            other => bail!("expected a number after {}, found {:?}", sign, other),
+        },
+        Some(Token::Symbol('(')) => {
+            parser.position -= 1;
+            let expression = parser.bracketed()?;
+            Ok((Some(tokens_as_sql(&expression)), true))
+        }
+        other => bail!("unexpected default {:?}", other),
+    }
+}
+
+/// Parses every CREATE TABLE statement in a SQL dump into tables, in the same shape as they'd be
+/// introspected from the database. Any other statements are skipped.
+pub fn parse_tables(sql: &str) -> Result<Vec<Table>> {
+    let mut parser = Parser::new(tokenize(sql)?);
+    let mut tables: Vec<Table> = vec![];
+    while !parser.is_done() {
+        if parser.eat_keywords(&["DROP", "TABLE"]) {
+            // Older dumps stand a table in for each view until it's dropped for the view.
+            let _ = parser.eat_keywords(&["IF", "EXISTS"]);
+            while matches!(parser.peek(), Some(Token::Identifier(_) | Token::Word(_))) {
+                let name = parser.identifier()?;
+                tables.retain(|table| table.name != name);
+                let _ = parser.eat_symbol(',');
+            }
+            parser.skip_statement();
+            continue;
+        }
+        if !parser.eat_keyword("CREATE") {
+            parser.skip_statement();
+            continue;
+        }
+        let _ = parser.eat_keyword("TEMPORARY");
+        if !parser.eat_keyword("TABLE") {
+            parser.skip_statement();
+            continue;
+        }
+        let _ = parser.eat_keywords(&["IF", "NOT", "EXISTS"]);
+        let name = parser.identifier()?;
+        if parser.peek() != Some(&Token::Symbol('(')) {
+            // CREATE TABLE ... LIKE and ... AS SELECT can't be known from the dump alone.
+            parser.skip_statement();
+            continue;
+        }
+        let mut definition = TableDefinition {
+            name,
+            comment: None,
+            columns: vec![],
+            primary_key: vec![],
+            indexes: vec![],
+            foreign_keys: vec![],
+        };
+        for tokens in split_on_commas(parser.bracketed()?) {
+            definition
+                .parse_definition(tokens)
+                .map_err(|e| anyhow!("table {}: {}", definition.name, e))?;
+        }
+        // Of the table options only the comment makes it into the translations. The partitions
+        // come last and can have comments of their own.
+        while let Some(token) = parser.next() {
+            match token {
+                Token::Symbol(';') => break,
+                Token::Word(word) if word.eq_ignore_ascii_case("PARTITION") => {
+                    parser.skip_statement();
+                    break;
+                }
+                Token::Word(word) if word.eq_ignore_ascii_case("COMMENT") => {
+                    let _ = parser.eat_symbol('=');
+                    if let Some(Token::Str(comment)) = parser.peek() {
+                        definition.comment = Some(comment.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+        tables.push(Table::from(definition));
+    }
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tables)
+}
+
+/// Reads the tables out of a SQL dump on disk, such as the output of `mysqldump --no-data`.
+pub fn get_table_descriptions(path: &str) -> Result<Vec<Table>> {
+    let sql = fs::read_to_string(path)?;
+    parse_tables(&sql)
+}
+
+/// A SQL dump on disk.
+pub struct DdlRemote {
+    pub path: String,
+}
+
+impl Remote for DdlRemote {
+    fn get_table_descriptions(&self) -> Result<Vec<Table>> {
+        get_table_descriptions(&self.path)
+    }
+}
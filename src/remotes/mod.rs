@@ -1 +1,5 @@
+pub mod behaviour;
+pub mod ddl;
+pub mod postgres;
 pub mod sql;
+pub mod sqlite;
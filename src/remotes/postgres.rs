@@ -0,0 +1,246 @@
+use crate::remotes::behaviour::Remote;
+use crate::remotes::sql::{
+    describe_key, group_by_table, key_information_from_rows, Description, Dialect, Table, TableKeys,
+};
+use anyhow::Result;
+use postgres::{Client, NoTls};
+use std::collections::HashMap;
+
+/// A live PostgreSQL database. Only the connection's current schema is introspected.
+pub struct PostgresRemote {
+    pub url: String,
+}
+
+impl Remote for PostgresRemote {
+    fn get_table_descriptions(&self) -> Result<Vec<Table>> {
+        get_table_descriptions(&self.url)
+    }
+}
+
+/// Postgres stores referential actions as a single character.
+fn referential_rule(action: &str) -> String {
+    String::from(match action {
+        "c" => "CASCADE",
+        "n" => "SET NULL",
+        "d" => "SET DEFAULT",
+        "r" => "RESTRICT",
+        _ => "NO ACTION",
+    })
+}
+
+/// Reads a column default into the same shape a MySQL `DESCRIBE` would give it, so that the
+/// translators don't need to know about Postgres' casts and sequences.
+fn describe_default(default: Option<String>, extra: &mut Vec<String>) -> Option<String> {
+    let default = default?;
+    if default.starts_with("nextval(") {
+        extra.push(String::from("auto_increment"));
+        return None;
+    }
+    if default.eq_ignore_ascii_case("now()")
+        || default.to_uppercase().starts_with("CURRENT_TIMESTAMP")
+    {
+        extra.push(String::from("DEFAULT_GENERATED"));
+        return Some(String::from("CURRENT_TIMESTAMP"));
+    }
+    if default.eq_ignore_ascii_case("NULL") || default.starts_with("NULL::") {
+        return None;
+    }
+    // Literals come back cast to the column type, such as 'draft'::character varying.
+    if default.starts_with('\'') {
+        if let Some(end) = default.rfind("'::") {
+            return Some(default[1..end].replace("''", "'"));
+        }
+        return Some(default.trim_matches('\'').replace("''", "'"));
+    }
+    if default.contains('(') {
+        extra.push(String::from("DEFAULT_GENERATED"));
+    }
+    Some(default)
+}
+
+/// Introspects every table in the connection's current schema, in a fixed number of queries.
+pub fn get_table_descriptions(url: &str) -> Result<Vec<Table>> {
+    let mut client = Client::connect(url, NoTls)?;
+    let columns = client.query(
+        "
+SELECT
+    c.relname::text,
+    a.attname::text,
+    format_type(a.atttypid, a.atttypmod),
+    a.attnotnull,
+    pg_get_expr(d.adbin, d.adrelid),
+    a.attidentity::text,
+    a.attgenerated::text,
+    col_description(c.oid, a.attnum),
+    obj_description(c.oid, 'pg_class')
+FROM
+    pg_attribute a
+JOIN
+    pg_class c ON c.oid = a.attrelid
+JOIN
+    pg_namespace n ON n.oid = c.relnamespace
+LEFT JOIN
+    pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+WHERE
+    n.nspname = current_schema()
+    AND c.relkind IN ('r', 'p', 'v', 'm')
+    AND a.attnum > 0
+    AND NOT a.attisdropped
+ORDER BY
+    c.relname, a.attnum;
+    ",
+        &[],
+    )?;
+    let indexes = client.query(
+        "
+SELECT
+    t.relname::text,
+    i.relname::text,
+    ix.indisunique,
+    ix.indisprimary,
+    a.attname::text,
+    k.ordinality
+FROM
+    pg_index ix
+JOIN
+    pg_class t ON t.oid = ix.indrelid
+JOIN
+    pg_class i ON i.oid = ix.indexrelid
+JOIN
+    pg_namespace n ON n.oid = t.relnamespace
+CROSS JOIN LATERAL
+    unnest(ix.indkey::int2[]) WITH ORDINALITY AS k(attnum, ordinality)
+LEFT JOIN
+    pg_attribute a ON a.attrelid = t.oid AND a.attnum = k.attnum
+WHERE
+    n.nspname = current_schema()
+    AND k.ordinality <= ix.indnkeyatts
+ORDER BY
+    t.relname, i.relname, k.ordinality;
+    ",
+        &[],
+    )?;
+    let foreign_keys = client.query(
+        "
+SELECT
+    t.relname::text,
+    con.conname::text,
+    a.attname::text,
+    k.ordinality,
+    rt.relname::text,
+    ra.attname::text,
+    con.confupdtype::text,
+    con.confdeltype::text
+FROM
+    pg_constraint con
+JOIN
+    pg_class t ON t.oid = con.conrelid
+JOIN
+    pg_class rt ON rt.oid = con.confrelid
+JOIN
+    pg_namespace n ON n.oid = t.relnamespace
+CROSS JOIN LATERAL
+    unnest(con.conkey, con.confkey) WITH ORDINALITY AS k(attnum, ref_attnum, ordinality)
+JOIN
+    pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum
+JOIN
+    pg_attribute ra ON ra.attrelid = con.confrelid AND ra.attnum = k.ref_attnum
+WHERE
+    n.nspname = current_schema()
+    AND con.contype = 'f'
+ORDER BY
+    t.relname, con.conname, k.ordinality;
+    ",
+        &[],
+    )?;
+
+    let mut indexes = group_by_table(
+        indexes
+            .iter()
+            .map(|row| {
+                (
+                    row.get::<_, String>(0),
+                    (row.get(1), row.get(2), row.get(3), row.get(4), row.get(5)),
+                )
+            })
+            .collect(),
+    );
+    let mut foreign_keys = group_by_table(
+        foreign_keys
+            .iter()
+            .map(|row| {
+                (
+                    row.get::<_, String>(0),
+                    (
+                        row.get(1),
+                        row.get(2),
+                        row.get(3),
+                        row.get(4),
+                        row.get(5),
+                        referential_rule(row.get(6)),
+                        referential_rule(row.get(7)),
+                    ),
+                )
+            })
+            .collect(),
+    );
+
+    let mut table_names: Vec<String> = vec![];
+    let mut columns_by_table: HashMap<String, Vec<Description>> = HashMap::new();
+    let mut table_comments: HashMap<String, String> = HashMap::new();
+    for row in columns.iter() {
+        let table_name: String = row.get(0);
+        let field: String = row.get(1);
+        let not_null: bool = row.get(3);
+        let identity: String = row.get(5);
+        let generated: String = row.get(6);
+        let mut extra: Vec<String> = vec![];
+        let default = describe_default(row.get(4), &mut extra);
+        if !identity.is_empty() {
+            extra.push(String::from("auto_increment"));
+        }
+        if !generated.is_empty() {
+            extra.push(String::from("STORED GENERATED"));
+        }
+        let key = describe_key(
+            &field,
+            indexes.get(&table_name).map(Vec::as_slice).unwrap_or(&[]),
+        );
+        // Columns come back ordered by table, so a new name means a new table.
+        if table_names.last() != Some(&table_name) {
+            table_names.push(table_name.clone());
+            if let Some(comment) = row.get::<_, Option<String>>(8) {
+                table_comments.insert(table_name.clone(), comment);
+            }
+        }
+        columns_by_table.entry(table_name).or_default().push(
+            Description::from((
+                field,
+                row.get(2),
+                String::from(if not_null { "NO" } else { "YES" }),
+                key,
+                default,
+                extra.join(" "),
+            ))
+            .with_comment(row.get(7)),
+        );
+    }
+
+    let mut all_descriptions: Vec<Table> = vec![];
+    for table in table_names {
+        let as_info = key_information_from_rows(
+            &indexes.remove(&table).unwrap_or_default(),
+            &foreign_keys.remove(&table).unwrap_or_default(),
+        );
+        let comment = table_comments.remove(&table);
+        let description = columns_by_table.remove(&table).unwrap_or_default();
+        all_descriptions.push(Table {
+            name: table,
+            dialect: Dialect::Postgres,
+            description,
+            keys: TableKeys::from(as_info),
+            comment,
+        });
+    }
+    Ok(all_descriptions)
+}
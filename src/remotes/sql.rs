@@ -1,15 +1,49 @@
+use crate::remotes::behaviour::Remote;
 use anyhow::Result;
 use mysql::prelude::Queryable;
-use mysql::Error;
 use mysql::Pool;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// What happens to the referencing rows when a referenced row is deleted or updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferentialAction {
+    Cascade,
+    SetNull,
+    SetDefault,
+    Restrict,
+    NoAction,
+}
+
+impl ReferentialAction {
+    pub fn from_string(rule: &str) -> Option<ReferentialAction> {
+        match rule.to_uppercase().as_str() {
+            "CASCADE" => Some(ReferentialAction::Cascade),
+            "SET NULL" => Some(ReferentialAction::SetNull),
+            "SET DEFAULT" => Some(ReferentialAction::SetDefault),
+            "RESTRICT" => Some(ReferentialAction::Restrict),
+            "NO ACTION" => Some(ReferentialAction::NoAction),
+            _ => None,
+        }
+    }
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Self::Cascade => "CASCADE",
+            Self::SetNull => "SET NULL",
+            Self::SetDefault => "SET DEFAULT",
+            Self::Restrict => "RESTRICT",
+            Self::NoAction => "NO ACTION",
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct ForeignKey {
     pub constraint_name: String,
-    pub column_name: String,
+    pub column_names: Vec<String>,
     pub referenced_table_name: String,
-    pub referenced_column_name: String,
+    pub referenced_column_names: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
 }
 
 #[derive(Debug, Clone)]
@@ -18,16 +52,6 @@ pub struct IndexKey {
     pub column_name: String,
 }
 
-impl IndexKey {
-    #[allow(dead_code)]
-    fn to_prisma_directive(&self) -> String {
-        format!(
-            "@index([{}]), map: {}",
-            self.column_name, self.constraint_name
-        )
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct UniqueKey {
     pub constraint_name: String,
@@ -42,7 +66,7 @@ pub enum Key {
     Unique(Vec<UniqueKey>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TableKeys {
     pub keys: Vec<Key>,
 }
@@ -57,111 +81,132 @@ impl From<Vec<ForeignKeyInformation>> for TableKeys {
                 column_names,
             }]));
         }
-        for row in rows {
-            // Need to add other key types here
-            if row.referenced_column_name.is_some() && row.referenced_table_name.is_some() {
-                keys.push(Key::Foreign(ForeignKey {
-                    constraint_name: row.constraint_name.unwrap(),
-                    column_name: row.column_name.unwrap(),
+        let unique_constraint_names: Vec<String> = keys
+            .iter()
+            .filter_map(|key| match key {
+                Key::Unique(unique_keys) => Some(unique_keys[0].constraint_name.clone()),
+                _ => None,
+            })
+            .collect();
+        let index_data = group_multiindex_constraints(&rows);
+        for (index_name, column_names) in index_data {
+            // Primary and unique keys are backed by an index too, but are covered above.
+            if index_name == "PRIMARY" || unique_constraint_names.contains(&index_name) {
+                continue;
+            }
+            let mut index_keys: Vec<IndexKey> = column_names
+                .into_iter()
+                .map(|column_name| IndexKey {
+                    constraint_name: index_name.clone(),
+                    column_name,
+                })
+                .collect();
+            if index_keys.len() == 1 {
+                keys.push(Key::Index(index_keys.remove(0)));
+            } else {
+                keys.push(Key::MultiIndex(index_keys));
+            }
+        }
+        // A composite foreign key comes through as one row per column.
+        let mut foreign_key_rows: Vec<ForeignKeyInformation> = rows
+            .into_iter()
+            .filter(|row| {
+                row.referenced_column_name.is_some() && row.referenced_table_name.is_some()
+            })
+            .collect();
+        foreign_key_rows.sort_by_key(|row| (row.constraint_name.clone(), row.ordinal_position));
+        let mut foreign_keys: Vec<ForeignKey> = vec![];
+        for row in foreign_key_rows {
+            let constraint_name = row.constraint_name.unwrap();
+            let column_name = row.column_name.unwrap();
+            let referenced_column_name = row.referenced_column_name.unwrap();
+            match foreign_keys.last_mut() {
+                Some(foreign_key) if foreign_key.constraint_name == constraint_name => {
+                    foreign_key.column_names.push(column_name);
+                    foreign_key
+                        .referenced_column_names
+                        .push(referenced_column_name);
+                }
+                _ => foreign_keys.push(ForeignKey {
+                    constraint_name,
+                    column_names: vec![column_name],
                     referenced_table_name: row.referenced_table_name.unwrap(),
-                    referenced_column_name: row.referenced_column_name.unwrap(),
-                }));
+                    referenced_column_names: vec![referenced_column_name],
+                    on_delete: row
+                        .delete_rule
+                        .as_deref()
+                        .and_then(ReferentialAction::from_string),
+                    on_update: row
+                        .update_rule
+                        .as_deref()
+                        .and_then(ReferentialAction::from_string),
+                }),
             }
         }
+        keys.extend(foreign_keys.into_iter().map(Key::Foreign));
         TableKeys { keys }
     }
 }
 
-#[allow(dead_code)]
-fn group_multiindex_constraints(data: &Vec<ForeignKeyInformation>) -> HashMap<String, Vec<String>> {
-    data.iter()
-        .filter_map(|info| {
-            if info.constraint_type.as_deref() == Some("UNIQUE") {
-                info.constraint_name.clone().zip(info.column_name.clone())
-            } else {
-                None
-            }
-        })
-        .fold(HashMap::new(), |mut acc, (constraint_name, column_name)| {
-            acc.entry(constraint_name)
-                .or_insert_with(Vec::new)
-                .push(column_name);
-            acc
-        })
+/// Groups the columns of every index by index name, in the order they appear in the index.
+/// Indexes come out sorted by name, so the output doesn't change between runs.
+fn group_multiindex_constraints(data: &[ForeignKeyInformation]) -> BTreeMap<String, Vec<String>> {
+    let mut index_rows: Vec<&ForeignKeyInformation> = data
+        .iter()
+        .filter(|info| info.constraint_name.is_none())
+        .collect();
+    index_rows.sort_by_key(|info| info.seq_in_index);
+    index_rows
+        .into_iter()
+        .filter_map(|info| info.index_name.clone().zip(info.column_name.clone()))
+        .fold(
+            BTreeMap::new(),
+            |mut acc, (constraint_name, column_name)| {
+                acc.entry(constraint_name)
+                    .or_insert_with(Vec::new)
+                    .push(column_name);
+                acc
+            },
+        )
 }
 
-fn group_unique_constraints(data: &Vec<ForeignKeyInformation>) -> HashMap<String, Vec<String>> {
-    data.iter()
-        .filter_map(|info| {
-            if info.constraint_type.as_deref() == Some("UNIQUE") {
-                info.constraint_name.clone().zip(info.column_name.clone())
-            } else {
-                None
-            }
-        })
-        .fold(HashMap::new(), |mut acc, (constraint_name, column_name)| {
-            acc.entry(constraint_name)
-                .or_insert_with(Vec::new)
-                .push(column_name);
-            acc
-        })
+/// Groups the columns of every unique constraint by constraint name, in the order they appear
+/// in the constraint. Constraints come out sorted by name.
+fn group_unique_constraints(data: &[ForeignKeyInformation]) -> BTreeMap<String, Vec<String>> {
+    let mut unique_rows: Vec<&ForeignKeyInformation> = data
+        .iter()
+        .filter(|info| info.constraint_type.as_deref() == Some("UNIQUE"))
+        .collect();
+    unique_rows.sort_by_key(|info| info.ordinal_position);
+    unique_rows
+        .into_iter()
+        .filter_map(|info| info.constraint_name.clone().zip(info.column_name.clone()))
+        .fold(
+            BTreeMap::new(),
+            |mut acc, (constraint_name, column_name)| {
+                acc.entry(constraint_name)
+                    .or_insert_with(Vec::new)
+                    .push(column_name);
+                acc
+            },
+        )
 }
 
-type ForeignKeySQLResponse = (
-    Option<String>,
-    Option<String>,
-    Option<String>,
-    Option<usize>,
-    Option<String>,
-    Option<String>,
-    Option<String>,
-    Option<usize>,
-    Option<usize>,
-    Option<String>,
-);
-
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
-struct ForeignKeyInformation {
-    constraint_name: Option<String>,
-    constraint_type: Option<String>,
-    column_name: Option<String>,
-    ordinal_position: Option<usize>,
-    referenced_table_name: Option<String>,
-    referenced_column_name: Option<String>,
-    index_name: Option<String>,
-    seq_in_index: Option<usize>,
-    cardinality: Option<usize>,
-    index_type: Option<String>,
-}
-
-impl From<ForeignKeySQLResponse> for ForeignKeyInformation {
-    fn from(row: ForeignKeySQLResponse) -> Self {
-        let (
-            constraint_name,
-            constraint_type,
-            column_name,
-            ordinal_position,
-            referenced_table_name,
-            referenced_column_name,
-            index_name,
-            seq_in_index,
-            cardinality,
-            index_type,
-        ) = row;
-        ForeignKeyInformation {
-            constraint_name,
-            constraint_type,
-            column_name,
-            ordinal_position,
-            referenced_table_name,
-            referenced_column_name,
-            index_name,
-            seq_in_index,
-            cardinality,
-            index_type,
-        }
-    }
+pub(crate) struct ForeignKeyInformation {
+    pub(crate) constraint_name: Option<String>,
+    pub(crate) constraint_type: Option<String>,
+    pub(crate) column_name: Option<String>,
+    pub(crate) ordinal_position: Option<usize>,
+    pub(crate) referenced_table_name: Option<String>,
+    pub(crate) referenced_column_name: Option<String>,
+    pub(crate) index_name: Option<String>,
+    pub(crate) seq_in_index: Option<usize>,
+    pub(crate) cardinality: Option<usize>,
+    pub(crate) index_type: Option<String>,
+    pub(crate) update_rule: Option<String>,
+    pub(crate) delete_rule: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -172,6 +217,8 @@ pub struct Description {
     pub key: String,
     pub default: Option<String>,
     pub extra: String,
+    /// The column's comment, which `DESCRIBE` leaves out.
+    pub comment: Option<String>,
 }
 
 type FieldDescriptionSQLResponse = (String, String, String, String, Option<String>, String);
@@ -196,15 +243,353 @@ impl From<FieldDescriptionSQLResponse> for Description {
             key,
             default,
             extra,
+            comment: None,
+        }
+    }
+}
+
+impl Description {
+    /// The description with the column's comment, where an empty comment is no comment.
+    pub fn with_comment(mut self, comment: Option<String>) -> Description {
+        self.comment = comment.filter(|comment| !comment.is_empty());
+        self
+    }
+
+    /// The column's MySQL type, parsed from the type `DESCRIBE` printed.
+    pub fn column_type(&self) -> ColumnType {
+        ColumnType::from(self.type_.as_str())
+    }
+}
+
+/// The arguments and attributes of a numeric column type. For integers the precision is the
+/// display width, such as the 1 in `tinyint(1)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Numeric {
+    pub precision: Option<u32>,
+    pub scale: Option<u32>,
+    pub unsigned: bool,
+    pub zerofill: bool,
+}
+
+/// The length and character set of a character column type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Character {
+    pub length: Option<u32>,
+    pub charset: Option<String>,
+}
+
+/// A MySQL 8 column type, such as `decimal(10,2) unsigned` or `enum('draft','published')`.
+/// Synonyms are read as the type MySQL stores them as, so `bool` is a `tinyint(1)` and
+/// `numeric` a `decimal`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnType {
+    TinyInt(Numeric),
+    SmallInt(Numeric),
+    MediumInt(Numeric),
+    Int(Numeric),
+    BigInt(Numeric),
+    Decimal(Numeric),
+    Float(Numeric),
+    Double(Numeric),
+    Bit(Option<u32>),
+    Date,
+    DateTime(Option<u32>),
+    Timestamp(Option<u32>),
+    Time(Option<u32>),
+    Year,
+    Char(Character),
+    VarChar(Character),
+    TinyText(Character),
+    Text(Character),
+    MediumText(Character),
+    LongText(Character),
+    Binary(Option<u32>),
+    VarBinary(Option<u32>),
+    TinyBlob,
+    Blob(Option<u32>),
+    MediumBlob,
+    LongBlob,
+    Enum {
+        values: Vec<String>,
+        charset: Option<String>,
+    },
+    Set {
+        values: Vec<String>,
+        charset: Option<String>,
+    },
+    Json,
+    Geometry,
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+    /// A type this version doesn't know about, kept as it was written.
+    Other(String),
+}
+
+impl ColumnType {
+    /// The name MySQL prints for the type, without its arguments or attributes.
+    pub fn base_name(&self) -> &str {
+        match self {
+            ColumnType::TinyInt(_) => "tinyint",
+            ColumnType::SmallInt(_) => "smallint",
+            ColumnType::MediumInt(_) => "mediumint",
+            ColumnType::Int(_) => "int",
+            ColumnType::BigInt(_) => "bigint",
+            ColumnType::Decimal(_) => "decimal",
+            ColumnType::Float(_) => "float",
+            ColumnType::Double(_) => "double",
+            ColumnType::Bit(_) => "bit",
+            ColumnType::Date => "date",
+            ColumnType::DateTime(_) => "datetime",
+            ColumnType::Timestamp(_) => "timestamp",
+            ColumnType::Time(_) => "time",
+            ColumnType::Year => "year",
+            ColumnType::Char(_) => "char",
+            ColumnType::VarChar(_) => "varchar",
+            ColumnType::TinyText(_) => "tinytext",
+            ColumnType::Text(_) => "text",
+            ColumnType::MediumText(_) => "mediumtext",
+            ColumnType::LongText(_) => "longtext",
+            ColumnType::Binary(_) => "binary",
+            ColumnType::VarBinary(_) => "varbinary",
+            ColumnType::TinyBlob => "tinyblob",
+            ColumnType::Blob(_) => "blob",
+            ColumnType::MediumBlob => "mediumblob",
+            ColumnType::LongBlob => "longblob",
+            ColumnType::Enum { .. } => "enum",
+            ColumnType::Set { .. } => "set",
+            ColumnType::Json => "json",
+            ColumnType::Geometry => "geometry",
+            ColumnType::Point => "point",
+            ColumnType::LineString => "linestring",
+            ColumnType::Polygon => "polygon",
+            ColumnType::MultiPoint => "multipoint",
+            ColumnType::MultiLineString => "multilinestring",
+            ColumnType::MultiPolygon => "multipolygon",
+            ColumnType::GeometryCollection => "geometrycollection",
+            ColumnType::Other(type_) => type_,
+        }
+    }
+}
+
+/// Splits the inside of a type's parentheses into its arguments, unquoting enum and set values.
+fn parse_type_arguments(arguments: &str) -> Vec<String> {
+    let mut values: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut chars = arguments.chars().peekable();
+    let mut in_quotes = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if in_quotes && chars.peek() == Some(&'\'') => {
+                chars.next();
+                current.push('\'');
+            }
+            '\'' => in_quotes = !in_quotes,
+            ',' if !in_quotes => values.push(std::mem::take(&mut current)),
+            _ if in_quotes || !c.is_whitespace() => current.push(c),
+            _ => {}
+        }
+    }
+    values.push(current);
+    values
+}
+
+impl From<&str> for ColumnType {
+    fn from(type_: &str) -> Self {
+        let type_ = type_.trim();
+        let (name, arguments, rest) = match (type_.find('('), type_.rfind(')')) {
+            (Some(start), Some(end)) if start < end => (
+                &type_[..start],
+                parse_type_arguments(&type_[start + 1..end]),
+                &type_[end + 1..],
+            ),
+            _ => match type_.find(' ') {
+                Some(space) => (&type_[..space], vec![], &type_[space..]),
+                None => (type_, vec![], ""),
+            },
+        };
+        let name = name.trim().to_lowercase();
+        let rest = rest.to_lowercase();
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        let charset = words
+            .iter()
+            .position(|word| *word == "charset" || *word == "set")
+            .and_then(|position| words.get(position + 1))
+            .map(|charset| charset.to_string());
+        let number = |index: usize| -> Option<u32> { arguments.get(index)?.parse().ok() };
+        let numeric = Numeric {
+            precision: number(0),
+            scale: number(1),
+            unsigned: words.contains(&"unsigned"),
+            zerofill: words.contains(&"zerofill"),
+        };
+        let character = Character {
+            length: number(0),
+            charset: charset.clone(),
+        };
+        match name.as_str() {
+            "tinyint" => ColumnType::TinyInt(numeric),
+            "bool" | "boolean" => ColumnType::TinyInt(Numeric {
+                precision: Some(1),
+                ..numeric
+            }),
+            "smallint" => ColumnType::SmallInt(numeric),
+            "mediumint" => ColumnType::MediumInt(numeric),
+            "int" | "integer" => ColumnType::Int(numeric),
+            "bigint" => ColumnType::BigInt(numeric),
+            "serial" => ColumnType::BigInt(Numeric {
+                unsigned: true,
+                ..numeric
+            }),
+            "decimal" | "dec" | "numeric" | "fixed" => ColumnType::Decimal(numeric),
+            "float" => ColumnType::Float(numeric),
+            "double" | "real" => ColumnType::Double(numeric),
+            "bit" => ColumnType::Bit(number(0)),
+            "date" => ColumnType::Date,
+            "datetime" => ColumnType::DateTime(number(0)),
+            "timestamp" => ColumnType::Timestamp(number(0)),
+            "time" => ColumnType::Time(number(0)),
+            "year" => ColumnType::Year,
+            "char" | "nchar" => ColumnType::Char(character),
+            "varchar" | "nvarchar" => ColumnType::VarChar(character),
+            "tinytext" => ColumnType::TinyText(character),
+            "text" => ColumnType::Text(character),
+            "mediumtext" => ColumnType::MediumText(character),
+            "longtext" => ColumnType::LongText(character),
+            "binary" => ColumnType::Binary(number(0)),
+            "varbinary" => ColumnType::VarBinary(number(0)),
+            "tinyblob" => ColumnType::TinyBlob,
+            "blob" => ColumnType::Blob(number(0)),
+            "mediumblob" => ColumnType::MediumBlob,
+            "longblob" => ColumnType::LongBlob,
+            "enum" => ColumnType::Enum {
+                values: arguments,
+                charset,
+            },
+            "set" => ColumnType::Set {
+                values: arguments,
+                charset,
+            },
+            "json" => ColumnType::Json,
+            "geometry" => ColumnType::Geometry,
+            "point" => ColumnType::Point,
+            "linestring" => ColumnType::LineString,
+            "polygon" => ColumnType::Polygon,
+            "multipoint" => ColumnType::MultiPoint,
+            "multilinestring" => ColumnType::MultiLineString,
+            "multipolygon" => ColumnType::MultiPolygon,
+            "geometrycollection" | "geomcollection" => ColumnType::GeometryCollection,
+            _ => ColumnType::Other(type_.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ColumnType {
+    /// Prints the type the way `DESCRIBE` does, such as `int unsigned` or `varchar(255)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let length = |length: &Option<u32>| match length {
+            Some(length) => format!("({})", length),
+            None => String::new(),
+        };
+        let quoted = |values: &[String]| {
+            values
+                .iter()
+                .map(|value| format!("'{}'", value.replace('\'', "''")))
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+        let charset = |charset: &Option<String>| match charset {
+            Some(charset) => format!(" character set {}", charset),
+            None => String::new(),
+        };
+        match self {
+            ColumnType::TinyInt(numeric)
+            | ColumnType::SmallInt(numeric)
+            | ColumnType::MediumInt(numeric)
+            | ColumnType::Int(numeric)
+            | ColumnType::BigInt(numeric)
+            | ColumnType::Decimal(numeric)
+            | ColumnType::Float(numeric)
+            | ColumnType::Double(numeric) => {
+                write!(f, "{}", self.base_name())?;
+                match (numeric.precision, numeric.scale) {
+                    (Some(precision), Some(scale)) => write!(f, "({},{})", precision, scale)?,
+                    (Some(precision), None) => write!(f, "({})", precision)?,
+                    _ => {}
+                }
+                if numeric.unsigned {
+                    write!(f, " unsigned")?;
+                }
+                if numeric.zerofill {
+                    write!(f, " zerofill")?;
+                }
+                Ok(())
+            }
+            ColumnType::Bit(size)
+            | ColumnType::DateTime(size)
+            | ColumnType::Timestamp(size)
+            | ColumnType::Time(size)
+            | ColumnType::Binary(size)
+            | ColumnType::VarBinary(size)
+            | ColumnType::Blob(size) => write!(f, "{}{}", self.base_name(), length(size)),
+            ColumnType::Char(character)
+            | ColumnType::VarChar(character)
+            | ColumnType::TinyText(character)
+            | ColumnType::Text(character)
+            | ColumnType::MediumText(character)
+            | ColumnType::LongText(character) => write!(
+                f,
+                "{}{}{}",
+                self.base_name(),
+                length(&character.length),
+                charset(&character.charset)
+            ),
+            ColumnType::Enum { values, charset: c } | ColumnType::Set { values, charset: c } => {
+                write!(f, "{}({}){}", self.base_name(), quoted(values), charset(c))
+            }
+            _ => write!(f, "{}", self.base_name()),
+        }
+    }
+}
+
+/// The flavour of SQL a table was read from, which decides how its column types are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    #[default]
+    MySql,
+    Postgres,
+    Sqlite,
+}
+
+impl Dialect {
+    pub fn from_string(dialect: &str) -> Option<Dialect> {
+        match dialect {
+            "mysql" => Some(Dialect::MySql),
+            "postgres" => Some(Dialect::Postgres),
+            "sqlite" => Some(Dialect::Sqlite),
+            _ => None,
+        }
+    }
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Self::MySql => "mysql",
+            Self::Postgres => "postgres",
+            Self::Sqlite => "sqlite",
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Table {
     pub name: String,
+    pub dialect: Dialect,
     pub description: Vec<Description>,
     pub keys: TableKeys,
+    pub comment: Option<String>,
 }
 
 impl std::fmt::Display for Table {
@@ -217,121 +602,536 @@ impl std::fmt::Display for Table {
     }
 }
 
-struct ErrorReport {
-    table: String,
-    error: Error,
+/// (table name, column name, column type, nullable, key, default, extra, comment), in the same
+/// shape `DESCRIBE` returns once the table name is taken off, with the comment added.
+type ColumnSQLResponse = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    Option<String>,
+    String,
+    String,
+);
+
+/// (table name, comment)
+type TableCommentSQLResponse = (String, String);
+
+/// (table name, constraint name, constraint type)
+type TableConstraintSQLResponse = (String, String, String);
+
+/// (table name, constraint name, column name, ordinal position, referenced table, referenced column)
+type KeyColumnUsageSQLResponse = (
+    String,
+    String,
+    String,
+    Option<usize>,
+    Option<String>,
+    Option<String>,
+);
+
+/// (table name, constraint name, update rule, delete rule)
+type ReferentialConstraintSQLResponse = (String, String, String, String);
+
+/// (table name, index name, column name, seq in index, cardinality, index type)
+type StatisticsSQLResponse = (
+    String,
+    String,
+    Option<String>,
+    Option<usize>,
+    Option<usize>,
+    Option<String>,
+);
+
+/// One column of the schema, from `INFORMATION_SCHEMA.COLUMNS`.
+#[derive(Debug, Clone)]
+pub struct ColumnRow {
+    pub table_name: String,
+    pub description: Description,
+}
+
+impl From<ColumnSQLResponse> for ColumnRow {
+    fn from(row: ColumnSQLResponse) -> Self {
+        let (table_name, field, type_, null, key, default, extra, comment) = row;
+        ColumnRow {
+            table_name,
+            description: Description::from((field, type_, null, key, default, extra))
+                .with_comment(Some(comment)),
+        }
+    }
+}
+
+/// A table's comment, from `INFORMATION_SCHEMA.TABLES`.
+#[derive(Debug, Clone)]
+pub struct TableCommentRow {
+    pub table_name: String,
+    pub comment: String,
 }
 
-impl std::fmt::Display for ErrorReport {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "Table: {}, Error: {}", self.table, self.error)
+impl From<TableCommentSQLResponse> for TableCommentRow {
+    fn from(row: TableCommentSQLResponse) -> Self {
+        let (table_name, comment) = row;
+        TableCommentRow {
+            table_name,
+            comment,
+        }
     }
 }
 
-pub fn get_table_descriptions(url: &str) -> Result<Vec<Table>> {
-    let pool = Pool::new(url)?;
-    let mut conn = pool.get_conn().unwrap();
-    let mut errors: Vec<ErrorReport> = vec![];
-    let tables: Vec<String> = conn.query("SHOW TABLES").unwrap_or_else(|e| {
-        errors.push(ErrorReport {
-            table: String::from("All tables"),
-            error: e,
+/// One constraint, from `INFORMATION_SCHEMA.TABLE_CONSTRAINTS`.
+#[derive(Debug, Clone)]
+pub struct TableConstraintRow {
+    pub table_name: String,
+    pub constraint_name: String,
+    pub constraint_type: String,
+}
+
+impl From<TableConstraintSQLResponse> for TableConstraintRow {
+    fn from(row: TableConstraintSQLResponse) -> Self {
+        let (table_name, constraint_name, constraint_type) = row;
+        TableConstraintRow {
+            table_name,
+            constraint_name,
+            constraint_type,
+        }
+    }
+}
+
+/// One column of a constraint, from `INFORMATION_SCHEMA.KEY_COLUMN_USAGE`.
+#[derive(Debug, Clone)]
+pub struct KeyColumnUsageRow {
+    pub table_name: String,
+    pub constraint_name: String,
+    pub column_name: String,
+    pub ordinal_position: Option<usize>,
+    pub referenced_table_name: Option<String>,
+    pub referenced_column_name: Option<String>,
+}
+
+impl From<KeyColumnUsageSQLResponse> for KeyColumnUsageRow {
+    fn from(row: KeyColumnUsageSQLResponse) -> Self {
+        let (
+            table_name,
+            constraint_name,
+            column_name,
+            ordinal_position,
+            referenced_table_name,
+            referenced_column_name,
+        ) = row;
+        KeyColumnUsageRow {
+            table_name,
+            constraint_name,
+            column_name,
+            ordinal_position,
+            referenced_table_name,
+            referenced_column_name,
+        }
+    }
+}
+
+/// The rules of a foreign key, from `INFORMATION_SCHEMA.REFERENTIAL_CONSTRAINTS`.
+#[derive(Debug, Clone)]
+pub struct ReferentialConstraintRow {
+    pub table_name: String,
+    pub constraint_name: String,
+    pub update_rule: String,
+    pub delete_rule: String,
+}
+
+impl From<ReferentialConstraintSQLResponse> for ReferentialConstraintRow {
+    fn from(row: ReferentialConstraintSQLResponse) -> Self {
+        let (table_name, constraint_name, update_rule, delete_rule) = row;
+        ReferentialConstraintRow {
+            table_name,
+            constraint_name,
+            update_rule,
+            delete_rule,
+        }
+    }
+}
+
+/// One column of an index, from `INFORMATION_SCHEMA.STATISTICS`.
+#[derive(Debug, Clone)]
+pub struct StatisticsRow {
+    pub table_name: String,
+    pub index_name: String,
+    pub column_name: Option<String>,
+    pub seq_in_index: Option<usize>,
+    pub cardinality: Option<usize>,
+    pub index_type: Option<String>,
+}
+
+impl From<StatisticsSQLResponse> for StatisticsRow {
+    fn from(row: StatisticsSQLResponse) -> Self {
+        let (table_name, index_name, column_name, seq_in_index, cardinality, index_type) = row;
+        StatisticsRow {
+            table_name,
+            index_name,
+            column_name,
+            seq_in_index,
+            cardinality,
+            index_type,
+        }
+    }
+}
+
+/// Groups query rows by their first element, the table name.
+pub(crate) fn group_by_table<T>(rows: Vec<(String, T)>) -> HashMap<String, Vec<T>> {
+    rows.into_iter()
+        .fold(HashMap::new(), |mut acc, (table_name, row)| {
+            acc.entry(table_name).or_insert_with(Vec::new).push(row);
+            acc
+        })
+}
+
+/// One column of an index, (index name, is unique, is primary, column name, position).
+pub(crate) type IndexRow = (String, bool, bool, Option<String>, i64);
+
+/// One column of a foreign key, (constraint name, column name, position, referenced table,
+/// referenced column, update rule, delete rule).
+pub(crate) type ForeignKeyRow = (String, String, i64, String, String, String, String);
+
+/// The Key column a MySQL `DESCRIBE` would show for a column.
+pub(crate) fn describe_key(column_name: &str, indexes: &[IndexRow]) -> String {
+    let is_in = |row: &&IndexRow| row.3.as_deref() == Some(column_name);
+    if indexes.iter().filter(is_in).any(|row| row.2) {
+        return String::from("PRI");
+    }
+    let first_in: Vec<&IndexRow> = indexes
+        .iter()
+        .filter(is_in)
+        .filter(|row| row.4 == 1)
+        .collect();
+    let is_single_column =
+        |index_name: &str| indexes.iter().filter(|row| row.0 == index_name).count() == 1;
+    if first_in.iter().any(|row| row.1 && is_single_column(&row.0)) {
+        return String::from("UNI");
+    }
+    if !first_in.is_empty() {
+        return String::from("MUL");
+    }
+    String::new()
+}
+
+/// Builds the key information for one table from catalog index and foreign key rows, shaped
+/// like the MySQL introspection's rows.
+pub(crate) fn key_information_from_rows(
+    indexes: &[IndexRow],
+    foreign_keys: &[ForeignKeyRow],
+) -> Vec<ForeignKeyInformation> {
+    let empty_row = ForeignKeyInformation {
+        constraint_name: None,
+        constraint_type: None,
+        column_name: None,
+        ordinal_position: None,
+        referenced_table_name: None,
+        referenced_column_name: None,
+        index_name: None,
+        seq_in_index: None,
+        cardinality: None,
+        index_type: None,
+        update_rule: None,
+        delete_rule: None,
+    };
+    let mut rows: Vec<ForeignKeyInformation> = vec![];
+    for (index_name, is_unique, is_primary, column_name, position) in indexes {
+        // The primary key is read from the columns instead.
+        if *is_primary {
+            continue;
+        }
+        let position = Some(*position as usize);
+        if *is_unique {
+            rows.push(ForeignKeyInformation {
+                constraint_name: Some(index_name.clone()),
+                constraint_type: Some(String::from("UNIQUE")),
+                column_name: column_name.clone(),
+                ordinal_position: position,
+                ..empty_row.clone()
+            });
+        }
+        rows.push(ForeignKeyInformation {
+            column_name: column_name.clone(),
+            ordinal_position: position,
+            index_name: Some(index_name.clone()),
+            seq_in_index: position,
+            ..empty_row.clone()
+        });
+    }
+    for (constraint_name, column_name, position, table, column, update_rule, delete_rule) in
+        foreign_keys
+    {
+        rows.push(ForeignKeyInformation {
+            constraint_name: Some(constraint_name.clone()),
+            constraint_type: Some(String::from("FOREIGN KEY")),
+            column_name: Some(column_name.clone()),
+            ordinal_position: Some(*position as usize),
+            referenced_table_name: Some(table.clone()),
+            referenced_column_name: Some(column.clone()),
+            update_rule: Some(update_rule.clone()),
+            delete_rule: Some(delete_rule.clone()),
+            ..empty_row.clone()
         });
-        vec![]
+    }
+    rows.sort_by(|a, b| {
+        (&a.constraint_name, a.ordinal_position).cmp(&(&b.constraint_name, b.ordinal_position))
     });
-    let mut all_descriptions: Vec<Table> = vec![];
-    for table in tables {
-        let descriptions: Vec<FieldDescriptionSQLResponse> = conn
-            .query(format!("DESCRIBE {}", &table))
-            .unwrap_or_else(|e| {
-                errors.push(ErrorReport {
-                    table: table.clone(),
-                    error: e,
-                });
-                vec![]
+    rows
+}
+
+/// Builds the key information for one table from the whole schema's constraint and index rows,
+/// ordered the same way as the key rows were when they were queried per table.
+fn get_key_information(
+    constraints: &[TableConstraintRow],
+    key_column_usage: &[KeyColumnUsageRow],
+    referential_constraints: &[ReferentialConstraintRow],
+    statistics: &[StatisticsRow],
+) -> Vec<ForeignKeyInformation> {
+    let mut rows: Vec<ForeignKeyInformation> = vec![];
+    for constraint in constraints {
+        if constraint.constraint_type == "PRIMARY KEY" || constraint.constraint_type == "CHECK" {
+            continue;
+        }
+        let rules = referential_constraints
+            .iter()
+            .find(|rules| rules.constraint_name == constraint.constraint_name);
+        for usage in key_column_usage
+            .iter()
+            .filter(|usage| usage.constraint_name == constraint.constraint_name)
+        {
+            rows.push(ForeignKeyInformation {
+                constraint_name: Some(constraint.constraint_name.clone()),
+                constraint_type: Some(constraint.constraint_type.clone()),
+                column_name: Some(usage.column_name.clone()),
+                ordinal_position: usage.ordinal_position,
+                referenced_table_name: usage.referenced_table_name.clone(),
+                referenced_column_name: usage.referenced_column_name.clone(),
+                index_name: None,
+                seq_in_index: None,
+                cardinality: None,
+                index_type: None,
+                update_rule: rules.map(|rules| rules.update_rule.clone()),
+                delete_rule: rules.map(|rules| rules.delete_rule.clone()),
             });
-        let relations_query = format!(
-            "
+        }
+    }
+    for statistic in statistics {
+        rows.push(ForeignKeyInformation {
+            constraint_name: None,
+            constraint_type: None,
+            column_name: statistic.column_name.clone(),
+            ordinal_position: statistic.seq_in_index,
+            referenced_table_name: None,
+            referenced_column_name: None,
+            index_name: Some(statistic.index_name.clone()),
+            seq_in_index: statistic.seq_in_index,
+            cardinality: statistic.cardinality,
+            index_type: statistic.index_type.clone(),
+            update_rule: None,
+            delete_rule: None,
+        });
+    }
+    rows.sort_by(|a, b| {
+        (&a.constraint_name, a.ordinal_position).cmp(&(&b.constraint_name, b.ordinal_position))
+    });
+    rows
+}
+
+/// Introspects every table in the connection's schema. Each part of the schema is pulled in a
+/// single query and the tables are assembled in memory, so the number of round trips doesn't grow
+/// with the number of tables.
+pub fn get_table_descriptions(url: &str) -> Result<Vec<Table>> {
+    let pool = Pool::new(url)?;
+    let mut conn = pool.get_conn()?;
+    let columns: Vec<ColumnSQLResponse> = conn.query(
+        "
 SELECT
-    constraint_name,
-    constraint_type,
-    column_name,
-    ordinal_position,
-    referenced_table_name,
-    referenced_column_name,
-    index_name,
-    seq_in_index,
-    cardinality,
-    index_type
-FROM (
-    SELECT
-        TC.CONSTRAINT_NAME AS constraint_name,
-        TC.CONSTRAINT_TYPE AS constraint_type,
-        KCU.COLUMN_NAME AS column_name,
-        KCU.REFERENCED_TABLE_NAME as referenced_table_name,
-        KCU.REFERENCED_COLUMN_NAME as referenced_column_name,
-        KCU.ORDINAL_POSITION AS ordinal_position,
-        NULL AS index_name,
-        NULL AS seq_in_index,
-        NULL AS cardinality,
-        NULL AS index_type
-    FROM
-        INFORMATION_SCHEMA.TABLE_CONSTRAINTS TC
-    JOIN
-        INFORMATION_SCHEMA.KEY_COLUMN_USAGE KCU
-    ON
-        TC.CONSTRAINT_NAME = KCU.CONSTRAINT_NAME
-    JOIN
-        INFORMATION_SCHEMA.COLUMNS C
-    ON
-        KCU.COLUMN_NAME = C.COLUMN_NAME
-        AND KCU.TABLE_NAME = C.TABLE_NAME
-        AND KCU.TABLE_SCHEMA = C.TABLE_SCHEMA
-    WHERE
-        TC.TABLE_NAME = '{}'
-        AND C.TABLE_NAME IS NOT NULL
-        AND TC.CONSTRAINT_TYPE NOT IN ('PRIMARY KEY', 'CHECK')
-    UNION ALL
-    SELECT
-        NULL AS constraint_name,
-        NULL AS constraint_type,
-        COLUMN_NAME AS column_name,
-        NULL as referenced_table_name,
-        NULL as referenced_column_name,
-        SEQ_IN_INDEX AS ordinal_position,
-        INDEX_NAME,
-        SEQ_IN_INDEX,
-        CARDINALITY,
-        INDEX_TYPE
-    FROM
-        INFORMATION_SCHEMA.STATISTICS
-    WHERE
-        TABLE_NAME = '{}'
-) AS combined_data
+    TABLE_NAME,
+    COLUMN_NAME,
+    COLUMN_TYPE,
+    IS_NULLABLE,
+    COLUMN_KEY,
+    COLUMN_DEFAULT,
+    EXTRA,
+    COLUMN_COMMENT
+FROM
+    INFORMATION_SCHEMA.COLUMNS
+WHERE
+    TABLE_SCHEMA = DATABASE()
 ORDER BY
-    constraint_name, ordinal_position;
+    TABLE_NAME, ORDINAL_POSITION;
     ",
-            &table, &table
-        );
-        let relations: Vec<ForeignKeySQLResponse> =
-            conn.query(relations_query).unwrap_or_else(|e| {
-                errors.push(ErrorReport {
-                    table: table.clone(),
-                    error: e,
-                });
-                vec![]
-            });
-        let as_info: Vec<ForeignKeyInformation> = relations
+    )?;
+    let table_comments: Vec<TableCommentSQLResponse> = conn.query(
+        "
+SELECT
+    TABLE_NAME,
+    TABLE_COMMENT
+FROM
+    INFORMATION_SCHEMA.TABLES
+WHERE
+    TABLE_SCHEMA = DATABASE();
+    ",
+    )?;
+    let constraints: Vec<TableConstraintSQLResponse> = conn.query(
+        "
+SELECT
+    TABLE_NAME,
+    CONSTRAINT_NAME,
+    CONSTRAINT_TYPE
+FROM
+    INFORMATION_SCHEMA.TABLE_CONSTRAINTS
+WHERE
+    TABLE_SCHEMA = DATABASE();
+    ",
+    )?;
+    let key_column_usage: Vec<KeyColumnUsageSQLResponse> = conn.query(
+        "
+SELECT
+    TABLE_NAME,
+    CONSTRAINT_NAME,
+    COLUMN_NAME,
+    ORDINAL_POSITION,
+    REFERENCED_TABLE_NAME,
+    REFERENCED_COLUMN_NAME
+FROM
+    INFORMATION_SCHEMA.KEY_COLUMN_USAGE
+WHERE
+    TABLE_SCHEMA = DATABASE();
+    ",
+    )?;
+    let referential_constraints: Vec<ReferentialConstraintSQLResponse> = conn.query(
+        "
+SELECT
+    TABLE_NAME,
+    CONSTRAINT_NAME,
+    UPDATE_RULE,
+    DELETE_RULE
+FROM
+    INFORMATION_SCHEMA.REFERENTIAL_CONSTRAINTS
+WHERE
+    CONSTRAINT_SCHEMA = DATABASE();
+    ",
+    )?;
+    let statistics: Vec<StatisticsSQLResponse> = conn.query(
+        "
+SELECT
+    TABLE_NAME,
+    INDEX_NAME,
+    COLUMN_NAME,
+    SEQ_IN_INDEX,
+    CARDINALITY,
+    INDEX_TYPE
+FROM
+    INFORMATION_SCHEMA.STATISTICS
+WHERE
+    TABLE_SCHEMA = DATABASE();
+    ",
+    )?;
+
+    Ok(assemble_tables(
+        columns.into_iter().map(ColumnRow::from).collect(),
+        statistics.into_iter().map(StatisticsRow::from).collect(),
+        key_column_usage
             .into_iter()
-            .map(ForeignKeyInformation::from)
-            .collect();
-        if descriptions.len() > 0 {
-            all_descriptions.push(Table {
-                name: table,
-                description: descriptions.into_iter().map(Description::from).collect(),
-                keys: TableKeys::from(as_info),
-            });
+            .map(KeyColumnUsageRow::from)
+            .collect(),
+        constraints
+            .into_iter()
+            .map(TableConstraintRow::from)
+            .collect(),
+        referential_constraints
+            .into_iter()
+            .map(ReferentialConstraintRow::from)
+            .collect(),
+        table_comments
+            .into_iter()
+            .map(TableCommentRow::from)
+            .collect(),
+    ))
+}
+
+/// Builds every table from the rows of the schema-wide queries, ordered the way the columns
+/// came back.
+pub fn assemble_tables(
+    columns: Vec<ColumnRow>,
+    statistics: Vec<StatisticsRow>,
+    key_column_usage: Vec<KeyColumnUsageRow>,
+    constraints: Vec<TableConstraintRow>,
+    referential_constraints: Vec<ReferentialConstraintRow>,
+    table_comments: Vec<TableCommentRow>,
+) -> Vec<Table> {
+    let mut table_names: Vec<String> = vec![];
+    let mut columns_by_table: HashMap<String, Vec<Description>> = HashMap::new();
+    for column in columns {
+        // Columns come back ordered by table, so a new name means a new table.
+        if table_names.last() != Some(&column.table_name) {
+            table_names.push(column.table_name.clone());
         }
+        columns_by_table
+            .entry(column.table_name)
+            .or_default()
+            .push(column.description);
+    }
+    let mut table_comments: HashMap<String, String> = table_comments
+        .into_iter()
+        .map(|row| (row.table_name, row.comment))
+        .collect();
+    let mut constraints = group_by_table(
+        constraints
+            .into_iter()
+            .map(|row| (row.table_name.clone(), row))
+            .collect(),
+    );
+    let mut key_column_usage = group_by_table(
+        key_column_usage
+            .into_iter()
+            .map(|row| (row.table_name.clone(), row))
+            .collect(),
+    );
+    let mut referential_constraints = group_by_table(
+        referential_constraints
+            .into_iter()
+            .map(|row| (row.table_name.clone(), row))
+            .collect(),
+    );
+    let mut statistics = group_by_table(
+        statistics
+            .into_iter()
+            .map(|row| (row.table_name.clone(), row))
+            .collect(),
+    );
+
+    let mut all_descriptions: Vec<Table> = vec![];
+    for table in table_names {
+        let as_info = get_key_information(
+            &constraints.remove(&table).unwrap_or_default(),
+            &key_column_usage.remove(&table).unwrap_or_default(),
+            &referential_constraints.remove(&table).unwrap_or_default(),
+            &statistics.remove(&table).unwrap_or_default(),
+        );
+        let description = columns_by_table.remove(&table).unwrap_or_default();
+        let comment = table_comments
+            .remove(&table)
+            .filter(|comment| !comment.is_empty());
+        all_descriptions.push(Table {
+            name: table,
+            dialect: Dialect::MySql,
+            description,
+            keys: TableKeys::from(as_info),
+            comment,
+        });
+    }
+    all_descriptions
+}
+
+/// A live MySQL database.
+pub struct MySqlRemote {
+    pub url: String,
+}
+
+impl Remote for MySqlRemote {
+    fn get_table_descriptions(&self) -> Result<Vec<Table>> {
+        get_table_descriptions(&self.url)
     }
-    Ok(all_descriptions)
 }
@@ -0,0 +1,204 @@
+use crate::remotes::behaviour::Remote;
+use crate::remotes::sql::{
+    describe_key, key_information_from_rows, Description, Dialect, ForeignKeyRow, IndexRow, Table,
+    TableKeys,
+};
+use anyhow::Result;
+use rusqlite::{Connection, OpenFlags};
+
+/// A SQLite database file, addressed either by its path or a `sqlite://` url.
+pub struct SqliteRemote {
+    pub url: String,
+}
+
+impl Remote for SqliteRemote {
+    fn get_table_descriptions(&self) -> Result<Vec<Table>> {
+        get_table_descriptions(&self.url)
+    }
+}
+
+/// One column from `PRAGMA table_info`, (name, type, not null, default, primary key position).
+type ColumnRow = (String, String, bool, Option<String>, i64);
+
+/// One row of `PRAGMA foreign_key_list`, (id, position, referenced table, column, referenced
+/// column, update rule, delete rule).
+type ForeignKeyListRow = (i64, i64, String, String, Option<String>, String, String);
+
+/// Reads a column default into the same shape a MySQL `DESCRIBE` would give it.
+fn describe_default(default: Option<String>, extra: &mut Vec<String>) -> Option<String> {
+    let default = default?;
+    if default.eq_ignore_ascii_case("NULL") {
+        return None;
+    }
+    if default.to_uppercase().starts_with("CURRENT_") {
+        extra.push(String::from("DEFAULT_GENERATED"));
+        return Some(default.to_uppercase());
+    }
+    if (default.starts_with('\'') || default.starts_with('"')) && default.len() >= 2 {
+        let quote = &default[..1];
+        if default.ends_with(quote) {
+            return Some(
+                default[1..default.len() - 1].replace(&format!("{}{}", quote, quote), quote),
+            );
+        }
+    }
+    if default.starts_with('(') {
+        extra.push(String::from("DEFAULT_GENERATED"));
+    }
+    Some(default)
+}
+
+fn get_indexes(connection: &Connection, table: &str) -> Result<Vec<IndexRow>> {
+    let mut index_list =
+        connection.prepare("SELECT name, \"unique\", origin FROM pragma_index_list(?1)")?;
+    let indexes = index_list
+        .query_map([table], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, bool>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let mut index_info =
+        connection.prepare("SELECT name FROM pragma_index_info(?1) ORDER BY seqno")?;
+    let mut rows: Vec<IndexRow> = vec![];
+    for (index_name, is_unique, origin) in indexes {
+        let column_names = index_info
+            .query_map([&index_name], |row| row.get::<_, Option<String>>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        for (position, column_name) in column_names.into_iter().enumerate() {
+            rows.push((
+                index_name.clone(),
+                is_unique,
+                origin == "pk",
+                column_name,
+                position as i64 + 1,
+            ));
+        }
+    }
+    Ok(rows)
+}
+
+fn get_foreign_keys(connection: &Connection, table: &str) -> Result<Vec<ForeignKeyRow>> {
+    let mut foreign_key_list = connection.prepare(
+        "SELECT id, seq, \"table\", \"from\", \"to\", on_update, on_delete
+         FROM pragma_foreign_key_list(?1) ORDER BY id, seq",
+    )?;
+    let rows = foreign_key_list
+        .query_map([table], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<ForeignKeyListRow>>>()?;
+    let mut primary_key_column =
+        connection.prepare("SELECT name FROM pragma_table_info(?1) WHERE pk = ?2")?;
+    let mut ids: Vec<i64> = rows.iter().map(|row| row.0).collect();
+    ids.dedup();
+    let mut foreign_keys: Vec<ForeignKeyRow> = vec![];
+    for id in ids {
+        let columns: Vec<&ForeignKeyListRow> = rows.iter().filter(|row| row.0 == id).collect();
+        // Foreign keys in SQLite have no names, so they're named the way Prisma names its own.
+        let column_names: Vec<&str> = columns.iter().map(|row| row.3.as_str()).collect();
+        let constraint_name = format!("{}_{}_fkey", table, column_names.join("_"));
+        for (_, seq, referenced_table, column, referenced_column, on_update, on_delete) in columns {
+            // A missing column refers to the referenced table's primary key.
+            let referenced_column = match referenced_column {
+                Some(referenced_column) => referenced_column.clone(),
+                None => {
+                    primary_key_column.query_row((referenced_table, seq + 1), |row| row.get(0))?
+                }
+            };
+            foreign_keys.push((
+                constraint_name.clone(),
+                column.clone(),
+                seq + 1,
+                referenced_table.clone(),
+                referenced_column,
+                on_update.to_uppercase(),
+                on_delete.to_uppercase(),
+            ));
+        }
+    }
+    Ok(foreign_keys)
+}
+
+/// Introspects every table in the database file.
+pub fn get_table_descriptions(url: &str) -> Result<Vec<Table>> {
+    let path = url.strip_prefix("sqlite://").unwrap_or(url);
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut table_list = connection.prepare(
+        "SELECT name FROM sqlite_master
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+         ORDER BY name",
+    )?;
+    let table_names = table_list
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let mut table_info = connection
+        .prepare("SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1)")?;
+
+    let mut all_descriptions: Vec<Table> = vec![];
+    for table in table_names {
+        let columns = table_info
+            .query_map([&table], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<ColumnRow>>>()?;
+        let indexes = get_indexes(&connection, &table)?;
+        let foreign_keys = get_foreign_keys(&connection, &table)?;
+        let number_of_primary_keys = columns.iter().filter(|column| column.4 > 0).count();
+
+        let mut description: Vec<Description> = vec![];
+        for (field, type_, not_null, default, primary_key) in columns {
+            let mut extra: Vec<String> = vec![];
+            let default = describe_default(default, &mut extra);
+            // A lone INTEGER PRIMARY KEY is an alias for the rowid, which counts up by itself.
+            if primary_key > 0
+                && number_of_primary_keys == 1
+                && type_.eq_ignore_ascii_case("INTEGER")
+            {
+                extra.push(String::from("auto_increment"));
+            }
+            let key = if primary_key > 0 {
+                String::from("PRI")
+            } else {
+                describe_key(&field, &indexes)
+            };
+            description.push(Description::from((
+                field,
+                type_,
+                String::from(if not_null || primary_key > 0 {
+                    "NO"
+                } else {
+                    "YES"
+                }),
+                key,
+                default,
+                extra.join(" "),
+            )));
+        }
+        all_descriptions.push(Table {
+            name: table,
+            dialect: Dialect::Sqlite,
+            description,
+            keys: TableKeys::from(key_information_from_rows(&indexes, &foreign_keys)),
+            // SQLite has nowhere to keep a comment besides the CREATE TABLE statement itself.
+            comment: None,
+        });
+    }
+    Ok(all_descriptions)
+}
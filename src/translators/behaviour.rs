@@ -1,17 +1,69 @@
 use crate::remotes::sql::Table;
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 /// A trait for translating between a database of Vec<Table> and the
 /// format implemented by the child struct.
-pub trait TranslatorBehaviour<T> {
+pub trait TranslatorBehaviour {
+    /// The format's own representation of the tables, such as a Prisma schema.
+    type Translation;
     /// Writes the database output straight to the disk in the desired format.
-    fn write_to_disk(&self, descriptions: &Vec<Table>) -> Result<()>;
-    /// Gets type T from a Vec of Table.
-    fn get_translation(&self, descriptions: &Vec<Table>) -> T;
+    fn write_to_disk(&self, descriptions: &[Table]) -> Result<()>;
+    /// Gets the translation from the tables.
+    fn get_translation(&self, descriptions: &[Table]) -> Self::Translation;
     /// Reads the database descriptions from the database in the desired format.
-    fn load_from_database(&mut self, descriptions: &Vec<Table>);
+    fn load_from_database(&mut self, descriptions: &[Table]);
     /// Reads the database description from disk in the desired format.
     fn load_from_disk(&mut self) -> Result<()>;
     /// Pretty print the translator's output.
     fn get_string(&self) -> String;
+    /// Reads the output loaded from disk back into tables, for formats that keep enough of
+    /// the database to do so.
+    fn to_tables(&self) -> Result<Vec<Table>> {
+        bail!("this format can't be read back into tables")
+    }
+    /// Whether the output loaded from disk is shown next to the database's, to see what
+    /// changed in it.
+    fn compares_with_database(&self) -> bool {
+        false
+    }
+    /// Anything about the tables the format can't represent, for the user to look at.
+    fn warnings(&self, _descriptions: &[Table]) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// The object-safe side of a translator, so the session can drive every format the same way.
+/// Every TranslatorBehaviour is a Translator.
+pub trait Translator {
+    fn write_to_disk(&self, descriptions: &[Table]) -> Result<()>;
+    fn load_from_database(&mut self, descriptions: &[Table]);
+    fn load_from_disk(&mut self) -> Result<()>;
+    fn get_string(&self) -> String;
+    fn to_tables(&self) -> Result<Vec<Table>>;
+    fn compares_with_database(&self) -> bool;
+    fn warnings(&self, descriptions: &[Table]) -> Vec<String>;
+}
+
+impl<T: TranslatorBehaviour> Translator for T {
+    fn write_to_disk(&self, descriptions: &[Table]) -> Result<()> {
+        TranslatorBehaviour::write_to_disk(self, descriptions)
+    }
+    fn load_from_database(&mut self, descriptions: &[Table]) {
+        TranslatorBehaviour::load_from_database(self, descriptions)
+    }
+    fn load_from_disk(&mut self) -> Result<()> {
+        TranslatorBehaviour::load_from_disk(self)
+    }
+    fn get_string(&self) -> String {
+        TranslatorBehaviour::get_string(self)
+    }
+    fn to_tables(&self) -> Result<Vec<Table>> {
+        TranslatorBehaviour::to_tables(self)
+    }
+    fn compares_with_database(&self) -> bool {
+        TranslatorBehaviour::compares_with_database(self)
+    }
+    fn warnings(&self, descriptions: &[Table]) -> Vec<String> {
+        TranslatorBehaviour::warnings(self, descriptions)
+    }
 }
@@ -0,0 +1,231 @@
+use crate::remotes::sql::{
+    describe_key, key_information_from_rows, Description, Dialect, ForeignKeyRow, IndexRow, Key,
+    Table, TableKeys,
+};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// The version of the structured document, raised whenever its layout changes in a way readers
+/// would notice.
+pub const DOCUMENT_VERSION: u32 = 1;
+
+/// The structured JSON output, every table with its columns and keys.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JsonDocument {
+    pub version: u32,
+    pub tables: Vec<JsonTable>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JsonTable {
+    pub name: String,
+    /// The dialect the table was read in, such as `mysql`.
+    pub dialect: String,
+    pub comment: Option<String>,
+    pub columns: Vec<JsonColumn>,
+    /// The primary key's columns, empty when the table has none.
+    pub primary_key: Vec<String>,
+    pub unique_constraints: Vec<JsonConstraint>,
+    pub indexes: Vec<JsonConstraint>,
+    pub foreign_keys: Vec<JsonForeignKey>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JsonColumn {
+    pub name: String,
+    /// The type as the database prints it, such as `varchar(255)`.
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub nullable: bool,
+    pub default: Option<String>,
+    /// What `DESCRIBE` shows in its Extra column, such as `auto_increment`.
+    pub extra: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// A unique constraint or index over one or more columns.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JsonConstraint {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JsonForeignKey {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+    /// The referential actions, such as `CASCADE`, or None where the database didn't say.
+    pub on_delete: Option<String>,
+    pub on_update: Option<String>,
+}
+
+impl From<&[Table]> for JsonDocument {
+    fn from(tables: &[Table]) -> Self {
+        JsonDocument {
+            version: DOCUMENT_VERSION,
+            tables: tables.iter().map(JsonTable::from).collect(),
+        }
+    }
+}
+
+impl From<&Table> for JsonTable {
+    fn from(table: &Table) -> Self {
+        let mut json_table = JsonTable {
+            name: table.name.clone(),
+            dialect: table.dialect.as_string().to_string(),
+            comment: table.comment.clone(),
+            columns: table.description.iter().map(JsonColumn::from).collect(),
+            primary_key: table
+                .description
+                .iter()
+                .filter(|description| description.key.contains("PRI"))
+                .map(|description| description.field.clone())
+                .collect(),
+            unique_constraints: vec![],
+            indexes: vec![],
+            foreign_keys: vec![],
+        };
+        for key in table.keys.keys.iter() {
+            match key {
+                Key::Unique(unique_keys) => {
+                    for unique_key in unique_keys {
+                        json_table.unique_constraints.push(JsonConstraint {
+                            name: unique_key.constraint_name.clone(),
+                            columns: unique_key.column_names.clone(),
+                        });
+                    }
+                }
+                Key::Index(index_key) => json_table.indexes.push(JsonConstraint {
+                    name: index_key.constraint_name.clone(),
+                    columns: vec![index_key.column_name.clone()],
+                }),
+                Key::MultiIndex(index_keys) => json_table.indexes.push(JsonConstraint {
+                    name: index_keys[0].constraint_name.clone(),
+                    columns: index_keys
+                        .iter()
+                        .map(|index_key| index_key.column_name.clone())
+                        .collect(),
+                }),
+                Key::Foreign(foreign_key) => json_table.foreign_keys.push(JsonForeignKey {
+                    name: foreign_key.constraint_name.clone(),
+                    columns: foreign_key.column_names.clone(),
+                    referenced_table: foreign_key.referenced_table_name.clone(),
+                    referenced_columns: foreign_key.referenced_column_names.clone(),
+                    on_delete: foreign_key
+                        .on_delete
+                        .map(|action| action.as_string().to_string()),
+                    on_update: foreign_key
+                        .on_update
+                        .map(|action| action.as_string().to_string()),
+                }),
+            }
+        }
+        json_table
+    }
+}
+
+impl From<&Description> for JsonColumn {
+    fn from(description: &Description) -> Self {
+        JsonColumn {
+            name: description.field.clone(),
+            type_: description.type_.clone(),
+            nullable: description.null == "YES",
+            default: description.default.clone(),
+            extra: Some(description.extra.clone()).filter(|extra| !extra.is_empty()),
+            comment: description.comment.clone(),
+        }
+    }
+}
+
+impl JsonDocument {
+    /// Read the document's tables back, with the same keys introspecting them would have found.
+    pub fn to_tables(&self) -> Result<Vec<Table>> {
+        if self.version > DOCUMENT_VERSION {
+            bail!(
+                "the JSON document is version {}, newer than the supported version {}",
+                self.version,
+                DOCUMENT_VERSION
+            );
+        }
+        self.tables.iter().map(JsonTable::to_table).collect()
+    }
+}
+
+impl JsonTable {
+    pub fn to_table(&self) -> Result<Table> {
+        let dialect = match Dialect::from_string(&self.dialect) {
+            Some(dialect) => dialect,
+            None => bail!(
+                "table {} has an unknown dialect {}",
+                self.name,
+                self.dialect
+            ),
+        };
+        let mut indexes: Vec<IndexRow> = vec![];
+        let mut push_index = |name: &str, is_unique: bool, is_primary: bool, columns: &[String]| {
+            for (position, column) in columns.iter().enumerate() {
+                indexes.push((
+                    name.to_string(),
+                    is_unique,
+                    is_primary,
+                    Some(column.clone()),
+                    position as i64 + 1,
+                ));
+            }
+        };
+        push_index("PRIMARY", true, true, &self.primary_key);
+        for constraint in &self.unique_constraints {
+            push_index(&constraint.name, true, false, &constraint.columns);
+        }
+        for constraint in &self.indexes {
+            push_index(&constraint.name, false, false, &constraint.columns);
+        }
+        let mut foreign_keys: Vec<ForeignKeyRow> = vec![];
+        for foreign_key in &self.foreign_keys {
+            let columns = foreign_key
+                .columns
+                .iter()
+                .zip(foreign_key.referenced_columns.iter());
+            for (position, (column, referenced_column)) in columns.enumerate() {
+                foreign_keys.push((
+                    foreign_key.name.clone(),
+                    column.clone(),
+                    position as i64 + 1,
+                    foreign_key.referenced_table.clone(),
+                    referenced_column.clone(),
+                    foreign_key.on_update.clone().unwrap_or_default(),
+                    foreign_key.on_delete.clone().unwrap_or_default(),
+                ));
+            }
+        }
+        let description = self
+            .columns
+            .iter()
+            .map(|column| column.to_description(describe_key(&column.name, &indexes)))
+            .collect();
+        Ok(Table {
+            name: self.name.clone(),
+            dialect,
+            description,
+            keys: TableKeys::from(key_information_from_rows(&indexes, &foreign_keys)),
+            comment: self.comment.clone(),
+        })
+    }
+}
+
+impl JsonColumn {
+    /// The column's description, given the Key column `DESCRIBE` would show for it.
+    pub fn to_description(&self, key: String) -> Description {
+        Description {
+            field: self.name.clone(),
+            type_: self.type_.clone(),
+            null: String::from(if self.nullable { "YES" } else { "NO" }),
+            key,
+            default: self.default.clone(),
+            extra: self.extra.clone().unwrap_or_default(),
+            comment: self.comment.clone(),
+        }
+    }
+}
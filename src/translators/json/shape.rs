@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// How the JSON output is laid out.
+#[derive(Serialize, PartialEq, Eq, Deserialize, Copy, Clone, Debug, Default)]
+pub enum JsonShape {
+    /// A versioned document with each column, key and constraint as an object.
+    #[default]
+    Structured,
+    /// Each column flattened into a string such as `int PRI NOT NULL`, by table.
+    Legacy,
+}
+
+impl JsonShape {
+    pub fn from_string(shape: &str) -> Option<JsonShape> {
+        match shape {
+            "structured" => Some(JsonShape::Structured),
+            "legacy" => Some(JsonShape::Legacy),
+            _ => None,
+        }
+    }
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Self::Structured => "structured",
+            Self::Legacy => "legacy",
+        }
+    }
+    pub fn all_as_array() -> Vec<JsonShape> {
+        vec![JsonShape::Structured, JsonShape::Legacy]
+    }
+    pub fn all_as_str_array() -> Vec<&'static str> {
+        JsonShape::all_as_array()
+            .into_iter()
+            .map(|shape| shape.as_string())
+            .collect()
+    }
+}
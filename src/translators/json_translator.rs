@@ -1,8 +1,9 @@
-use crate::remotes::sql::{Description, Table};
+use crate::functionality::structure::DiskMapping;
+use crate::remotes::sql::{Description, ForeignKey, Key, Table};
 use crate::translators::behaviour::TranslatorBehaviour;
-use anyhow::Result;
-use serde_json::json;
-use std::collections::HashMap;
+use crate::translators::json::{document::JsonDocument, shape::JsonShape};
+use anyhow::{bail, Result};
+use serde_json::{json, Map, Value};
 use std::fs::File;
 use std::io::BufWriter;
 
@@ -10,24 +11,23 @@ use std::io::BufWriter;
 pub struct JsonTranslator {
     pub path: String,
     pub json: Option<serde_json::Value>,
+    pub shape: JsonShape,
 }
 
 /// Public implementation for JsonTranslator
-impl TranslatorBehaviour<serde_json::Value> for JsonTranslator {
+impl TranslatorBehaviour for JsonTranslator {
+    type Translation = serde_json::Value;
+
     /// Get json data from the database's output
-    fn get_translation(&self, database: &Vec<Table>) -> serde_json::Value {
-        let mut result = HashMap::new();
-        for database in database {
-            let this_result = json!(self.format_database(&database.description));
-            result.insert(&database.name, this_result);
+    fn get_translation(&self, database: &[Table]) -> serde_json::Value {
+        match self.shape {
+            JsonShape::Structured => json!(JsonDocument::from(database)),
+            JsonShape::Legacy => self.legacy_translation(database),
         }
-        let mut tables = HashMap::new();
-        tables.insert("tables", result);
-        json!(tables)
     }
 
     /// Load json from a database into the translator.
-    fn load_from_database(&mut self, database: &Vec<Table>) {
+    fn load_from_database(&mut self, database: &[Table]) {
         self.json = Some(self.get_translation(database))
     }
 
@@ -45,7 +45,7 @@ impl TranslatorBehaviour<serde_json::Value> for JsonTranslator {
     }
 
     /// Receive data from the datbase and write straight to disk.
-    fn write_to_disk(&self, database: &Vec<Table>) -> Result<()> {
+    fn write_to_disk(&self, database: &[Table]) -> Result<()> {
         println!("writing json to {}", &self.path);
         let the_json = self.get_translation(database);
         self.dump_json(&the_json)?;
@@ -54,17 +54,58 @@ impl TranslatorBehaviour<serde_json::Value> for JsonTranslator {
 
     /// A pretty string representation of the translator's json.
     fn get_string(&self) -> String {
-        return serde_json::to_string_pretty(self.json.as_ref().unwrap()).unwrap();
+        serde_json::to_string_pretty(self.json.as_ref().unwrap()).unwrap()
+    }
+
+    /// Read the loaded document back into tables. The legacy layout drops defaults and key
+    /// names, so only structured documents can be read back.
+    fn to_tables(&self) -> Result<Vec<Table>> {
+        let json = match &self.json {
+            Some(json) => json,
+            None => bail!("no JSON has been loaded from {}", self.path),
+        };
+        if json.get("version").is_none() {
+            bail!(
+                "{} is in the legacy layout, which can't be read back into tables",
+                self.path
+            );
+        }
+        let document: JsonDocument = serde_json::from_value(json.clone())?;
+        document.to_tables()
     }
 }
 
 /// Private implementation behaviours for JsonTranslator
 impl JsonTranslator {
-    /// Formats one field description.
-    fn format_table(&self, field: &Description) -> String {
+    /// The translator for a mapping's file, laid out in the mapping's shape.
+    pub fn from_mapping(mapping: &DiskMapping) -> JsonTranslator {
+        JsonTranslator {
+            path: mapping.path.clone(),
+            json: None,
+            shape: mapping.json_shape,
+        }
+    }
+
+    /// The legacy layout, each column flattened into a string by table.
+    fn legacy_translation(&self, database: &[Table]) -> serde_json::Value {
+        let mut result = Map::new();
+        for database in database {
+            let this_result = Value::Object(self.format_database(database));
+            result.insert(database.name.clone(), this_result);
+        }
+        // Table comments sit beside the tables, as a table's keys are all column names.
+        let descriptions: Map<String, Value> = database
+            .iter()
+            .filter_map(|table| Some((table.name.clone(), json!(table.comment.as_ref()?))))
+            .collect();
+        json!({ "tables": result, "descriptions": descriptions })
+    }
+
+    /// Formats one field description, along with the foreign key it belongs to if any.
+    fn format_table(&self, field: &Description, foreign_key: Option<&ForeignKey>) -> String {
         let mut result = String::new();
         result.push_str(&field.type_);
-        if field.key.len() > 0 {
+        if !field.key.is_empty() {
             result.push_str(&format!(" {}", field.key));
         }
         if field.null == "NO" {
@@ -73,15 +114,45 @@ impl JsonTranslator {
         if field.extra.contains("auto_increment") {
             result.push_str(" AUTO_INCREMENT");
         }
+        if let Some(foreign_key) = foreign_key {
+            if foreign_key.column_names.len() > 1 {
+                result.push_str(&format!(
+                    " FOREIGN KEY ({})",
+                    foreign_key.column_names.join(", ")
+                ));
+            }
+            result.push_str(&format!(
+                " REFERENCES {}({})",
+                foreign_key.referenced_table_name,
+                foreign_key.referenced_column_names.join(", ")
+            ));
+            if let Some(on_delete) = foreign_key.on_delete {
+                result.push_str(&format!(" ON DELETE {}", on_delete.as_string()));
+            }
+            if let Some(on_update) = foreign_key.on_update {
+                result.push_str(&format!(" ON UPDATE {}", on_update.as_string()));
+            }
+        }
+        if let Some(comment) = &field.comment {
+            result.push_str(&format!(" COMMENT '{}'", comment.replace('\'', "''")));
+        }
         result
     }
 
-    /// Coalesces the description of a database's tables into a hashmap.
-    fn format_database(&self, database: &Vec<Description>) -> HashMap<String, String> {
-        let mut result = HashMap::new();
-        // println!("{:?}", database);
-        for db in database {
-            result.insert(String::from(&db.field), self.format_table(db));
+    /// Coalesces the description of a table's columns into a map, in column order.
+    fn format_database(&self, table: &Table) -> Map<String, Value> {
+        let mut result = Map::new();
+        for db in &table.description {
+            let foreign_key = table.keys.keys.iter().find_map(|key| match key {
+                Key::Foreign(foreign_key) if foreign_key.column_names.contains(&db.field) => {
+                    Some(foreign_key)
+                }
+                _ => None,
+            });
+            result.insert(
+                String::from(&db.field),
+                Value::String(self.format_table(db, foreign_key)),
+            );
         }
         result
     }
@@ -1,4 +1,7 @@
 pub mod behaviour;
+pub mod json;
 pub mod json_translator;
+pub mod ordering;
 pub mod prisma;
 pub mod prisma_translator;
+pub mod registry;
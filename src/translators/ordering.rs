@@ -0,0 +1,68 @@
+use crate::remotes::sql::{Key, Table};
+use serde::{Deserialize, Serialize};
+
+/// The order tables are written out in. Columns always keep their position in the table.
+#[derive(Serialize, PartialEq, Eq, Deserialize, Copy, Clone, Debug, Default)]
+pub enum TableOrder {
+    /// By name.
+    #[default]
+    Alphabetical,
+    /// Referenced tables before the tables referring to them, by name where either could go
+    /// first. Tables referring to each other in a cycle go by name too.
+    Dependency,
+}
+
+impl TableOrder {
+    pub fn from_string(order: &str) -> Option<TableOrder> {
+        match order {
+            "alphabetical" => Some(TableOrder::Alphabetical),
+            "dependency" => Some(TableOrder::Dependency),
+            _ => None,
+        }
+    }
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Self::Alphabetical => "alphabetical",
+            Self::Dependency => "dependency",
+        }
+    }
+    pub fn all_as_array() -> Vec<TableOrder> {
+        vec![TableOrder::Alphabetical, TableOrder::Dependency]
+    }
+    pub fn all_as_str_array() -> Vec<&'static str> {
+        TableOrder::all_as_array()
+            .into_iter()
+            .map(|order| order.as_string())
+            .collect()
+    }
+
+    /// The tables in this order, so that the same tables are always written out the same way.
+    pub fn ordered(&self, tables: &[Table]) -> Vec<Table> {
+        let mut remaining: Vec<&Table> = tables.iter().collect();
+        remaining.sort_by(|a, b| a.name.cmp(&b.name));
+        if *self == TableOrder::Alphabetical {
+            return remaining.into_iter().cloned().collect();
+        }
+        let mut ordered: Vec<Table> = vec![];
+        while !remaining.is_empty() {
+            let is_ready = |table: &&Table| {
+                referenced_tables(table).all(|referenced| {
+                    referenced == table.name
+                        || ordered.iter().any(|done| done.name == referenced)
+                        || !remaining.iter().any(|other| other.name == referenced)
+                })
+            };
+            let next = remaining.iter().position(is_ready).unwrap_or(0);
+            ordered.push(remaining.remove(next).clone());
+        }
+        ordered
+    }
+}
+
+/// The names of the tables a table's foreign keys point at.
+fn referenced_tables(table: &Table) -> impl Iterator<Item = &str> {
+    table.keys.keys.iter().filter_map(|key| match key {
+        Key::Foreign(foreign_key) => Some(foreign_key.referenced_table_name.as_str()),
+        _ => None,
+    })
+}
@@ -1,42 +1,103 @@
+use crate::remotes::sql::Dialect;
+use crate::translators::prisma::options::{config_block, DatasourceOptions};
+use crate::translators::prisma::parser::{Block, Expression};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct Datasource {
     name: String,
     provider: String,
+    url_env: String,
+    relation_mode: Option<String>,
+    shadow_database_url_env: Option<String>,
+}
+
+impl Default for Datasource {
+    fn default() -> Datasource {
+        Datasource::new()
+    }
 }
 
 impl Datasource {
     pub fn new() -> Datasource {
-        Datasource {
-            name: String::from("db"),
-            provider: String::from("mysql"),
+        Datasource::for_dialect(Dialect::MySql)
+    }
+    /// The datasource for a database of the given dialect.
+    pub fn for_dialect(dialect: Dialect) -> Datasource {
+        let provider = match dialect {
+            Dialect::MySql => "mysql",
+            Dialect::Postgres => "postgresql",
+            Dialect::Sqlite => "sqlite",
+        };
+        let mut datasource = Datasource {
+            name: String::new(),
+            provider: String::from(provider),
+            url_env: String::new(),
+            relation_mode: None,
+            shadow_database_url_env: None,
+        };
+        datasource.configure(&DatasourceOptions::default());
+        datasource
+    }
+    /// The dialect of the database behind the datasource, None for providers without tables.
+    pub fn dialect(&self) -> Option<Dialect> {
+        match self.provider.as_str() {
+            "mysql" => Some(Dialect::MySql),
+            "postgresql" | "postgres" => Some(Dialect::Postgres),
+            "sqlite" => Some(Dialect::Sqlite),
+            _ => None,
         }
     }
+
+    pub fn get_provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// Set everything but the provider from a mapping's options.
+    pub fn configure(&mut self, options: &DatasourceOptions) {
+        self.name = options.name.clone();
+        self.url_env = options.url_env.clone();
+        self.relation_mode = options.relation_mode.clone();
+        self.shadow_database_url_env = options.shadow_database_url_env.clone();
+    }
     pub fn as_text(&self) -> String {
-        format!(
-            "datasource {} {{\n  provider = \"{}\"\n  url      = env(\"DATABASE_URL\")\n}}",
-            self.name, self.provider
-        )
-    }
-    pub fn parse_from_disk(datasource_string: &String) -> Datasource {
-        let lines: Vec<&str> = datasource_string.split("\n").collect();
-        let mut name = String::new();
-        let mut provider = String::new();
-        for line in lines {
-            if line.contains("datasource") {
-                name.push_str(
-                    line.strip_prefix("datasource ")
-                        .unwrap()
-                        .strip_suffix(" {")
-                        .unwrap(),
-                )
-            }
-            if line.contains("provider") {
-                let pieces: Vec<&str> = line.split("=").collect();
-                provider = pieces[1].replace('"', "").replace(' ', "");
-            }
+        let mut properties = vec![
+            ("provider", format!("\"{}\"", self.provider)),
+            ("url", format!("env(\"{}\")", self.url_env)),
+        ];
+        if let Some(shadow_database_url_env) = &self.shadow_database_url_env {
+            properties.push((
+                "shadowDatabaseUrl",
+                format!("env(\"{}\")", shadow_database_url_env),
+            ));
+        }
+        if let Some(relation_mode) = &self.relation_mode {
+            properties.push(("relationMode", format!("\"{}\"", relation_mode)));
+        }
+        config_block("datasource", &self.name, &properties)
+    }
+    pub fn from_block(block: &Block) -> Datasource {
+        // Urls are read from the environment, a url written out in full is left out.
+        let env = |name: &str| match block.property(name) {
+            Some(Expression::Function { name, arguments }) if name == "env" => arguments
+                .first()
+                .and_then(|argument| argument.value.as_str())
+                .map(str::to_string),
+            _ => None,
+        };
+        Datasource {
+            name: block.name.clone(),
+            provider: block
+                .property("provider")
+                .and_then(|provider| provider.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            url_env: env("url").unwrap_or_default(),
+            relation_mode: block
+                .property("relationMode")
+                .and_then(|relation_mode| relation_mode.as_str())
+                .map(str::to_string),
+            shadow_database_url_env: env("shadowDatabaseUrl"),
         }
-        Datasource { name, provider }
     }
 }
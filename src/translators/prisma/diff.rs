@@ -0,0 +1,256 @@
+use crate::translators::prisma::field::Field;
+use crate::translators::prisma::model::Model;
+use crate::translators::prisma::schema::PrismaSchema;
+
+/// One attribute of a field which differs, with its value on each side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeChange {
+    /// The attribute's name, such as `@default`, or `type` for the field's type and `///` for its
+    /// documentation.
+    pub attribute: &'static str,
+    pub disk: Option<String>,
+    pub database: Option<String>,
+}
+
+/// A field found on both sides with attributes which differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub name: String,
+    pub changes: Vec<AttributeChange>,
+}
+
+/// The differences within a model found on both sides.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ModelDiff {
+    pub name: String,
+    pub added_fields: Vec<String>,
+    pub removed_fields: Vec<String>,
+    pub changed_fields: Vec<FieldDiff>,
+    pub added_directives: Vec<String>,
+    pub removed_directives: Vec<String>,
+}
+
+impl ModelDiff {
+    /// The differences between a model on disk and the database's version of it.
+    pub fn between(disk: &Model, database: &Model) -> ModelDiff {
+        let mut diff = ModelDiff {
+            name: database.get_name().to_string(),
+            ..Default::default()
+        };
+        for field in database.get_fields() {
+            match disk.get_fields().iter().find(|f| f.name == field.name) {
+                Some(disk_field) => {
+                    let changes = field_changes(disk_field, field);
+                    if !changes.is_empty() {
+                        diff.changed_fields.push(FieldDiff {
+                            name: field.name.clone(),
+                            changes,
+                        });
+                    }
+                }
+                None => diff.added_fields.push(field.name.clone()),
+            }
+        }
+        for field in disk.get_fields() {
+            if !database.has_field(&field.name) {
+                diff.removed_fields.push(field.name.clone());
+            }
+        }
+        let disk_directives = disk.directive_lines();
+        let database_directives = database.directive_lines();
+        diff.added_directives = database_directives
+            .iter()
+            .filter(|directive| !disk_directives.contains(directive))
+            .cloned()
+            .collect();
+        diff.removed_directives = disk_directives
+            .iter()
+            .filter(|directive| !database_directives.contains(directive))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_fields.is_empty()
+            && self.removed_fields.is_empty()
+            && self.changed_fields.is_empty()
+            && self.added_directives.is_empty()
+            && self.removed_directives.is_empty()
+    }
+}
+
+/// The differences between a Prisma schema on disk and the schema derived from the database.
+/// Anything "added" is in the database but not on disk, and anything "removed" is on disk but
+/// no longer in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SchemaDiff {
+    pub added_models: Vec<String>,
+    pub removed_models: Vec<String>,
+    pub changed_models: Vec<ModelDiff>,
+    pub added_enums: Vec<String>,
+    pub removed_enums: Vec<String>,
+    pub changed_enums: Vec<String>,
+}
+
+impl SchemaDiff {
+    pub fn between(disk: &PrismaSchema, database: &PrismaSchema) -> SchemaDiff {
+        let mut diff = SchemaDiff::default();
+        for model in database.get_models() {
+            let disk_model = disk
+                .get_models()
+                .iter()
+                .find(|m| m.get_name() == model.get_name());
+            match disk_model {
+                Some(disk_model) => {
+                    let model_diff = ModelDiff::between(disk_model, model);
+                    if !model_diff.is_empty() {
+                        diff.changed_models.push(model_diff);
+                    }
+                }
+                None => diff.added_models.push(model.get_name().to_string()),
+            }
+        }
+        for model in disk.get_models() {
+            if !database
+                .get_models()
+                .iter()
+                .any(|m| m.get_name() == model.get_name())
+            {
+                diff.removed_models.push(model.get_name().to_string());
+            }
+        }
+        for enumeration in database.get_enums() {
+            let disk_enum = disk
+                .get_enums()
+                .iter()
+                .find(|e| e.get_name() == enumeration.get_name());
+            match disk_enum {
+                Some(disk_enum) if disk_enum != enumeration => {
+                    diff.changed_enums.push(enumeration.get_name().to_string())
+                }
+                Some(_) => {}
+                None => diff.added_enums.push(enumeration.get_name().to_string()),
+            }
+        }
+        for enumeration in disk.get_enums() {
+            if !database
+                .get_enums()
+                .iter()
+                .any(|e| e.get_name() == enumeration.get_name())
+            {
+                diff.removed_enums.push(enumeration.get_name().to_string());
+            }
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_models.is_empty()
+            && self.removed_models.is_empty()
+            && self.changed_models.is_empty()
+            && self.added_enums.is_empty()
+            && self.removed_enums.is_empty()
+            && self.changed_enums.is_empty()
+    }
+
+    /// A readable report, with `+` for what the database added, `-` for what it removed and `~`
+    /// for what it changed.
+    pub fn as_text(&self) -> String {
+        if self.is_empty() {
+            return String::from("no differences\n");
+        }
+        let mut text = String::new();
+        for name in self.added_models.iter() {
+            text.push_str(&format!("+ model {}\n", name));
+        }
+        for name in self.removed_models.iter() {
+            text.push_str(&format!("- model {}\n", name));
+        }
+        for model in self.changed_models.iter() {
+            text.push_str(&format!("~ model {}\n", model.name));
+            for name in model.added_fields.iter() {
+                text.push_str(&format!("    + {}\n", name));
+            }
+            for name in model.removed_fields.iter() {
+                text.push_str(&format!("    - {}\n", name));
+            }
+            for field in model.changed_fields.iter() {
+                text.push_str(&format!("    ~ {}\n", field.name));
+                for change in field.changes.iter() {
+                    text.push_str(&format!(
+                        "        {}: {} -> {}\n",
+                        change.attribute,
+                        change.disk.as_deref().unwrap_or("(none)"),
+                        change.database.as_deref().unwrap_or("(none)")
+                    ));
+                }
+            }
+            for directive in model.added_directives.iter() {
+                text.push_str(&format!("    + {}\n", directive));
+            }
+            for directive in model.removed_directives.iter() {
+                text.push_str(&format!("    - {}\n", directive));
+            }
+        }
+        for name in self.added_enums.iter() {
+            text.push_str(&format!("+ enum {}\n", name));
+        }
+        for name in self.removed_enums.iter() {
+            text.push_str(&format!("- enum {}\n", name));
+        }
+        for name in self.changed_enums.iter() {
+            text.push_str(&format!("~ enum {}\n", name));
+        }
+        text
+    }
+}
+
+/// Each attribute of the field as it would be written, or None where it's not set.
+fn attributes(field: &Field) -> [(&'static str, Option<String>); 9] {
+    [
+        ("type", Some(field.field_type.clone())),
+        ("@id", field.is_id.then(|| String::from("@id"))),
+        (
+            "@unique",
+            field.unique.as_ref().map(|unique| unique.clone().as_text()),
+        ),
+        (
+            "@default",
+            field
+                .default
+                .as_ref()
+                .map(|default| format!("@default({})", default)),
+        ),
+        (
+            "@updatedAt",
+            field.is_updated_at.then(|| String::from("@updatedAt")),
+        ),
+        ("@db", field.db_type_annotation.clone()),
+        (
+            "@relation",
+            field.relation.as_ref().map(|relation| relation.as_text()),
+        ),
+        (
+            "@map",
+            field.map.as_ref().map(|map| format!("@map(\"{}\")", map)),
+        ),
+        (
+            "///",
+            (!field.documentation.is_empty()).then(|| field.documentation.join(" ")),
+        ),
+    ]
+}
+
+fn field_changes(disk: &Field, database: &Field) -> Vec<AttributeChange> {
+    attributes(disk)
+        .into_iter()
+        .zip(attributes(database))
+        .filter(|((_, disk), (_, database))| disk != database)
+        .map(|((attribute, disk), (_, database))| AttributeChange {
+            attribute,
+            disk,
+            database,
+        })
+        .collect()
+}
@@ -0,0 +1,155 @@
+use crate::remotes::sql::{ColumnType, Dialect, Table};
+use crate::translators::prisma::parser::{Block, EnumValueDeclaration};
+use serde::{Deserialize, Serialize};
+
+/// One value of a Prisma enum, mapped back to the database's spelling when that spelling isn't
+/// a valid identifier.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct EnumValue {
+    pub name: String,
+    pub map: Option<String>,
+}
+
+impl EnumValue {
+    /// The Prisma value for a database value, replacing anything that can't be part of an
+    /// identifier the way `prisma db pull` does.
+    pub fn from_database(value: &str) -> EnumValue {
+        let name = identifier(value, "EMPTY_ENUM_VALUE");
+        let map = if name != value {
+            Some(value.to_string())
+        } else {
+            None
+        };
+        EnumValue { name, map }
+    }
+
+    pub fn as_text(&self) -> String {
+        match &self.map {
+            Some(map) => format!("{} @map(\"{}\")", self.name, map.replace('"', "\\\"")),
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn from_declaration(declaration: &EnumValueDeclaration) -> EnumValue {
+        let map = declaration
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name == "map")
+            .and_then(|attribute| attribute.argument(None))
+            .and_then(|map| map.as_str())
+            .map(str::to_string);
+        EnumValue {
+            name: declaration.name.clone(),
+            map,
+        }
+    }
+}
+
+/// A top-level Prisma enum block, such as one generated for a MySQL `ENUM` column.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Enum {
+    name: String,
+    values: Vec<EnumValue>,
+}
+
+impl Enum {
+    pub fn new(name: String, values: Vec<EnumValue>) -> Enum {
+        Enum { name, values }
+    }
+
+    /// The enum for a MySQL `ENUM` column, named after its table and column like
+    /// `prisma db pull` names it.
+    pub fn from_column(table_name: &str, column_name: &str, values: &[String]) -> Enum {
+        Enum {
+            name: enum_name(table_name, column_name),
+            values: values
+                .iter()
+                .map(|value| EnumValue::from_database(value))
+                .collect(),
+        }
+    }
+
+    /// The enums for each of a MySQL table's `ENUM` columns.
+    pub fn from_table(table: &Table) -> Vec<Enum> {
+        if table.dialect != Dialect::MySql {
+            return vec![];
+        }
+        table
+            .description
+            .iter()
+            .filter_map(|description| match description.column_type() {
+                ColumnType::Enum { values, .. } => {
+                    Some(Enum::from_column(&table.name, &description.field, &values))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_values(&self) -> &[EnumValue] {
+        &self.values
+    }
+
+    /// The Prisma value for a value as the database spells it.
+    pub fn find_value(&self, database_value: &str) -> Option<&EnumValue> {
+        self.values.iter().find(|value| match &value.map {
+            Some(map) => map == database_value,
+            None => value.name == database_value,
+        })
+    }
+
+    pub fn as_text(&self) -> String {
+        let mut text = String::new();
+        text.push_str(&format!("enum {} {{\n", self.name));
+        for value in &self.values {
+            text.push_str("  ");
+            text.push_str(&value.as_text());
+            text.push('\n');
+        }
+        text.push_str("}\n");
+        text
+    }
+
+    /// Build an enum from its block in a schema file.
+    pub fn from_block(block: &Block) -> Enum {
+        Enum {
+            name: block.name.clone(),
+            values: block
+                .values
+                .iter()
+                .map(EnumValue::from_declaration)
+                .collect(),
+        }
+    }
+}
+
+/// The name of the enum generated for a table's column.
+pub fn enum_name(table_name: &str, column_name: &str) -> String {
+    identifier(&format!("{}_{}", table_name, column_name), "EMPTY_ENUM")
+}
+
+/// Replaces every character that can't appear in a Prisma identifier with an underscore, and
+/// prefixes names that would otherwise start with a digit.
+fn identifier(value: &str, empty: &str) -> String {
+    if value.is_empty() {
+        return empty.to_string();
+    }
+    let mut name: String = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
@@ -1,7 +1,10 @@
-use crate::remotes::sql::Description;
+use crate::remotes::sql::{ColumnType, Description, Dialect, Numeric};
+use crate::translators::prisma::enumeration::Enum;
+use crate::translators::prisma::parser::FieldDeclaration;
 use crate::translators::prisma::relation::Relation;
 use crate::translators::prisma::unique_flag::UniqueFlag;
 use serde::{Deserialize, Serialize};
+use std::fmt::Write;
 
 #[derive(Deserialize, Serialize, Debug, Clone, Eq)]
 pub struct Field {
@@ -15,7 +18,12 @@ pub struct Field {
     pub is_id: bool,
     pub relation: Option<Relation>,
     pub default: Option<String>,
+    pub is_updated_at: bool,
     pub unique: Option<UniqueFlag>,
+    pub map: Option<String>,
+    /// The `///` lines written above the field, from the column's comment.
+    #[serde(default)]
+    pub documentation: Vec<String>,
 }
 
 impl Field {
@@ -31,147 +39,76 @@ impl Field {
             is_id: false,
             relation: None,
             default: None,
+            is_updated_at: false,
             unique: None,
+            map: None,
+            documentation: vec![],
         }
     }
-    pub fn parse_from_disk(field_str: &str) -> Option<Field> {
-        if field_str.starts_with("@@") || field_str.starts_with("@relation") {
-            panic!("uncaught directive {}", field_str);
-        }
-        let mut field_str_mut = field_str.to_string();
-        let mut name = String::new();
-        let mut field_type = String::new();
-        let mut db_type_annotation: Option<String> = None;
-        let mut relation: Option<Relation> = None;
-        let mut functions: Vec<String> = vec![];
-        let mut parsing_function = false;
-        let mut function_buffer = String::new();
-        let mut open_brackets = 0;
-        let mut open_squirlys = 0;
-        let mut open_parens = 0;
-        let mut empty_skipped = 0;
-        let mut is_array = false;
-        let mut is_required = true;
-        let mut is_id = false;
-        let mut default: Option<String> = None;
-        let mut unique: Option<UniqueFlag> = None;
-
-        // Handle unique piece and remove it if it exists
-        if field_str_mut.contains("@unique") {
-            let pieces: Vec<&str> = field_str.split("@").collect::<Vec<&str>>()[1..].to_vec();
-            let mut unique_piece = "@".to_string();
-            unique_piece.push_str(
-                pieces
-                    .into_iter()
-                    .filter(|piece| piece.contains("unique"))
-                    .collect::<Vec<&str>>()
-                    .pop()
-                    .unwrap(),
-            );
-            field_str_mut = field_str_mut.replace(unique_piece.as_str(), "");
-            unique = Some(UniqueFlag::from(unique_piece.as_str()));
-        }
-
-        // Iterate through the rest of the line as words
-        let pieces: Vec<&str> = field_str_mut.split(" ").collect();
-        for (i, piece) in pieces.into_iter().enumerate() {
-            if piece.len() == 0 {
-                empty_skipped = empty_skipped + 1;
-                continue;
-            }
-            if i - empty_skipped == 0 {
-                name.push_str(piece);
-                continue;
-            }
-            if i - empty_skipped == 1 {
-                field_type.push_str(piece);
-                if piece.contains("[]") {
-                    is_array = true;
-                }
-                if piece.contains("?") {
-                    is_required = false;
-                } else {
-                    is_required = true;
-                }
-                continue;
-            }
-            if piece.contains("@id") {
-                is_id = true;
-                continue;
-            }
-            if piece.contains("@default") {
-                default = Some(
-                    piece
-                        .strip_prefix("@default(")
-                        .expect("default to be formatted properly")
-                        .strip_suffix(")")
-                        .expect("default to be formatted properly")
-                        .to_string(),
-                )
-            }
-            if piece.contains("@db.") {
-                if db_type_annotation.is_some() {
-                    panic!("more than one db type annotation found");
-                } else {
-                    db_type_annotation = Some(piece.to_string());
+    /// Build a field from its declaration in a schema file.
+    pub fn from_declaration(declaration: &FieldDeclaration) -> Field {
+        let mut field = Field::new();
+        field.set_name(declaration.name.clone());
+        field.set_field_type(declaration.field_type.as_text());
+        // A list can always be empty, so it's never required.
+        field.set_is_required(
+            !declaration.field_type.is_optional && !declaration.field_type.is_list,
+        );
+        field.is_array = declaration.field_type.is_list;
+        field.documentation = declaration.documentation.clone();
+        for attribute in declaration.attributes.iter() {
+            match attribute.name.as_str() {
+                "id" => field.set_is_id(true),
+                "unique" => field.set_unique(Some(UniqueFlag {
+                    map: attribute
+                        .argument(Some("map"))
+                        .and_then(|map| map.as_str())
+                        .map(str::to_string),
+                })),
+                "default" => {
+                    field.default = attribute.argument(None).map(|value| value.as_text());
                 }
-            }
-            if piece.contains("(") || piece.contains("[") || piece.contains("{") {
-                parsing_function = true;
-                open_parens = open_parens + piece.matches("(").count();
-                open_brackets = open_brackets + piece.matches("[").count();
-                open_squirlys = open_squirlys + piece.matches("{").count();
-            }
-            if parsing_function {
-                let mut to_push = format!("{}", piece);
-                if to_push.ends_with(",") {
-                    to_push.push_str(" ")
+                "updatedAt" => field.is_updated_at = true,
+                "relation" => field.relation = Some(Relation::from_attribute(attribute)),
+                "map" => {
+                    field.map = attribute
+                        .argument(None)
+                        .and_then(|map| map.as_str())
+                        .map(str::to_string);
                 }
-                let _ = &function_buffer.push_str(to_push.as_str());
-            }
-            if piece.contains(")") || piece.contains("]") || piece.contains("}") {
-                open_parens = open_parens - piece.matches(")").count();
-                open_brackets = open_brackets - piece.matches("]").count();
-                open_squirlys = open_squirlys - piece.matches("}").count();
-                if open_parens == 0 && open_brackets == 0 && open_squirlys == 0 {
-                    parsing_function = false;
-                    if function_buffer.clone().len() > 0 {
-                        if function_buffer.starts_with("@db.") {
-                            db_type_annotation = Some(function_buffer.clone());
-                        } else if function_buffer.starts_with("@relation") {
-                            relation = Some(Relation::from_string(function_buffer.clone()));
-                        } else {
-                            functions.push(function_buffer.clone());
-                        }
-                        function_buffer.clear();
-                        continue;
-                    }
+                name if name.starts_with("db.") => {
+                    field.db_type_annotation = Some(attribute.as_text());
                 }
-                continue;
+                _ => {}
             }
-            continue;
-        }
-        if parsing_function {
-            println!("Unterminated delimiter in {}", field_str_mut);
-            return None;
         }
-        if name == "}" {
-            return None;
+        field.set_target_name_length(field.name.len());
+        field.set_field_type_length(field.field_type.len());
+        field
+    }
+    /// Build a field which points at another model rather than a database column.
+    pub fn new_relation(
+        name: String,
+        model_name: String,
+        is_required: bool,
+        is_array: bool,
+        relation: Option<Relation>,
+    ) -> Field {
+        let mut field = Field::new();
+        let mut field_type = model_name;
+        if is_array {
+            field_type.push_str("[]");
+        } else if !is_required {
+            field_type.push('?');
         }
-        let field = Field {
-            name,
-            target_name_width: 0,
-            field_type,
-            target_field_type_width: 0,
-            db_type_annotation,
-            is_array,
-            is_required,
-            is_id,
-            relation,
-            default,
-            unique,
-        };
-        Some(field)
+        field.set_name(name);
+        field.set_field_type(field_type);
+        field.set_is_required(is_required);
+        field.is_array = is_array;
+        field.relation = relation;
+        field.set_target_name_length(field.name.len());
+        field.set_field_type_length(field.field_type.len());
+        field
     }
     fn set_name(&mut self, name: String) {
         self.name = name;
@@ -184,9 +121,6 @@ impl Field {
     fn set_field_type(&mut self, field_type: String) {
         self.field_type = field_type;
     }
-    fn set_db_type_annotation(&mut self, db_type_annotation: String) {
-        self.db_type_annotation = Some(db_type_annotation);
-    }
     pub fn set_field_type_length(&mut self, length: usize) {
         if length > self.target_field_type_width {
             self.target_field_type_width = length;
@@ -211,263 +145,373 @@ impl Field {
         text.push_str(&self.name);
         let spaces_to_add = max_name_width - self.name.len();
         for _ in 0..spaces_to_add {
-            text.push_str(" ");
+            text.push(' ');
         }
-        text.push_str(" ");
+        text.push(' ');
         text.push_str(&self.field_type);
         let spaces_to_add = max_field_width - self.field_type.len();
         for _ in 0..spaces_to_add {
-            text.push_str(" ");
+            text.push(' ');
         }
-        text.push_str(" ");
+        text.push(' ');
         if self.is_id && number_of_id_fields == 1 {
             text.push_str("@id ");
         }
-        if self.unique.is_some() {
-            text.push_str(self.unique.as_ref().unwrap().clone().as_text().as_str());
-            text.push_str(" ");
+        if let Some(unique) = &self.unique {
+            text.push_str(&unique.clone().as_text());
+            text.push(' ');
         }
-        if self.default.is_some() {
-            text.push_str("@default(");
-            text.push_str(self.default.as_ref().unwrap().as_str());
-            text.push_str(") ");
-            text.push_str(" ");
+        if let Some(default) = &self.default {
+            let _ = write!(text, "@default({}) ", default);
         }
-        if self.db_type_annotation.is_some() {
-            text.push_str(
-                self.format_db_annotation()
-                    .expect(self.db_type_annotation.clone().unwrap().as_str())
-                    .as_str(),
-            );
-            text.push_str(" ");
+        if self.is_updated_at {
+            text.push_str("@updatedAt ");
         }
-        if self.relation.is_some() {
-            text.push_str(self.relation.clone().unwrap().as_text().as_str());
-            text.push_str(" ");
+        if let Some(map) = &self.map {
+            let _ = write!(text, "@map(\"{}\") ", map);
+        }
+        if let Some(annotation) = &self.db_type_annotation {
+            text.push_str(annotation);
+            text.push(' ');
+        }
+        if let Some(relation) = &self.relation {
+            text.push_str(&relation.as_text());
+            text.push(' ');
         }
         text
     }
-    fn get_field_type_from_db_type(&self) -> String {
-        let db_type = self
-            .db_type_annotation
-            .clone()
-            .unwrap_or("String".to_string());
-        let mut resp = String::new();
-        match db_type {
-            _ if db_type.eq("tinyint(1)") => resp.push_str("Boolean"),
-            _ if db_type.contains("tinyint") => resp.push_str("Int"),
-            _ if db_type.contains("smallint") => resp.push_str("Int"),
-            _ if db_type.contains("mediumint") => resp.push_str("Int"),
-            _ if db_type.contains("int") => resp.push_str("Int"),
-            _ if db_type.contains("bigint") => resp.push_str("Int"),
-            _ if db_type.contains("float") => resp.push_str("Float"),
-            _ if db_type.contains("double") => resp.push_str("Float"),
-            _ if db_type.contains("decimal") => resp.push_str("Decimal"),
-            _ if db_type.contains("date") => resp.push_str("DateTime"),
-            _ if db_type.contains("time") => resp.push_str("DateTime"),
-            _ if db_type.contains("timestamp") => resp.push_str("DateTime"),
-            _ if db_type.contains("year") => resp.push_str("Int"),
-            _ if db_type.contains("bool") => resp.push_str("Boolean"),
-            _ if db_type.contains("json") => resp.push_str("Json"),
-            _ => resp.push_str("String"),
+}
+
+/// A database comment split into the lines of a `///` documentation comment.
+pub fn documentation_lines(comment: Option<&str>) -> Vec<String> {
+    comment
+        .map(|comment| {
+            comment
+                .lines()
+                .map(|line| line.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Each line of a documentation comment as it's written, at the indentation given.
+pub fn documentation_text(documentation: &[String], indentation: &str) -> String {
+    documentation
+        .iter()
+        .map(|line| {
+            format!("{}/// {}", indentation, line)
+                .trim_end()
+                .to_string()
+                + "\n"
+        })
+        .collect()
+}
+
+/// The Prisma type and native type annotation for a MySQL column type, leaving out the
+/// annotation where it's the type's default, as `prisma db pull` does.
+fn mysql_field_type(column_type: &ColumnType) -> (String, Option<String>) {
+    let annotation = |annotation: &str| Some(format!("@db.{}", annotation));
+    let sized = |annotation: &str, size: &Option<u32>| {
+        Some(format!("@db.{}({})", annotation, size.unwrap_or(0)))
+    };
+    let integer = |annotation: &str, numeric: &Numeric| match numeric.unsigned {
+        true => Some(format!("@db.Unsigned{}", annotation)),
+        false => Some(format!("@db.{}", annotation)),
+    };
+    let (field_type, annotation) = match column_type {
+        ColumnType::TinyInt(numeric) if numeric.precision == Some(1) && !numeric.unsigned => {
+            ("Boolean", None)
         }
-        if !self.is_required {
-            resp.push_str("?");
+        ColumnType::TinyInt(numeric) => ("Int", integer("TinyInt", numeric)),
+        ColumnType::SmallInt(numeric) => ("Int", integer("SmallInt", numeric)),
+        ColumnType::MediumInt(numeric) => ("Int", integer("MediumInt", numeric)),
+        ColumnType::Int(numeric) if numeric.unsigned => ("Int", annotation("UnsignedInt")),
+        ColumnType::Int(_) => ("Int", None),
+        ColumnType::BigInt(numeric) if numeric.unsigned => ("BigInt", annotation("UnsignedBigInt")),
+        ColumnType::BigInt(_) => ("BigInt", None),
+        ColumnType::Decimal(numeric) => (
+            "Decimal",
+            Some(format!(
+                "@db.Decimal({}, {})",
+                numeric.precision.unwrap_or(10),
+                numeric.scale.unwrap_or(0)
+            )),
+        ),
+        ColumnType::Float(_) => ("Float", annotation("Float")),
+        ColumnType::Double(_) => ("Float", None),
+        ColumnType::Bit(None) | ColumnType::Bit(Some(1)) => ("Boolean", annotation("Bit(1)")),
+        ColumnType::Bit(size) => ("Bytes", sized("Bit", size)),
+        ColumnType::Date => ("DateTime", annotation("Date")),
+        ColumnType::DateTime(Some(3)) => ("DateTime", None),
+        ColumnType::DateTime(precision) => ("DateTime", sized("DateTime", precision)),
+        ColumnType::Timestamp(precision) => ("DateTime", sized("Timestamp", precision)),
+        ColumnType::Time(precision) => ("DateTime", sized("Time", precision)),
+        ColumnType::Year => ("Int", annotation("Year")),
+        ColumnType::Char(character) => (
+            "String",
+            Some(format!("@db.Char({})", character.length.unwrap_or(1))),
+        ),
+        ColumnType::VarChar(character) if character.length == Some(191) => ("String", None),
+        ColumnType::VarChar(character) => ("String", sized("VarChar", &character.length)),
+        ColumnType::TinyText(_) => ("String", annotation("TinyText")),
+        ColumnType::Text(_) => ("String", annotation("Text")),
+        ColumnType::MediumText(_) => ("String", annotation("MediumText")),
+        ColumnType::LongText(_) => ("String", annotation("LongText")),
+        ColumnType::Binary(size) => ("Bytes", sized("Binary", size)),
+        ColumnType::VarBinary(size) => ("Bytes", sized("VarBinary", size)),
+        ColumnType::TinyBlob => ("Bytes", annotation("TinyBlob")),
+        ColumnType::Blob(_) => ("Bytes", annotation("Blob")),
+        ColumnType::MediumBlob => ("Bytes", annotation("MediumBlob")),
+        ColumnType::LongBlob => ("Bytes", None),
+        ColumnType::Enum { .. } | ColumnType::Set { .. } => ("String", None),
+        ColumnType::Json => ("Json", None),
+        ColumnType::Geometry
+        | ColumnType::Point
+        | ColumnType::LineString
+        | ColumnType::Polygon
+        | ColumnType::MultiPoint
+        | ColumnType::MultiLineString
+        | ColumnType::MultiPolygon
+        | ColumnType::GeometryCollection => {
+            return (
+                format!("Unsupported(\"{}\")", column_type.base_name()),
+                None,
+            )
         }
-        resp
+        ColumnType::Other(db_type) => return (format!("Unsupported(\"{}\")", db_type), None),
+    };
+    (field_type.to_string(), annotation)
+}
+
+/// The Prisma type and native type annotation for a column type as Postgres' `format_type`
+/// prints it, such as `character varying(255)` or `timestamp(3) without time zone`.
+fn postgres_field_type(db_type: &str) -> (String, Option<String>) {
+    let (base, is_array) = match db_type.strip_suffix("[]") {
+        Some(base) => (base, true),
+        None => (db_type, false),
+    };
+    let arguments = base
+        .find('(')
+        .and_then(|start| base.find(')').map(|end| &base[start + 1..end]));
+    let name = match base.find('(') {
+        Some(start) => {
+            let end = base.find(')').map(|end| end + 1).unwrap_or(base.len());
+            format!("{}{}", &base[..start], &base[end..])
+        }
+        None => base.to_string(),
+    };
+    let with_arguments = |annotation: &str| match arguments {
+        Some(arguments) => format!("@db.{}({})", annotation, arguments.replace(',', ", ")),
+        None => format!("@db.{}", annotation),
+    };
+    let (field_type, annotation) = match name.as_str() {
+        "integer" => ("Int", None),
+        "smallint" => ("Int", Some(String::from("@db.SmallInt"))),
+        "bigint" => ("BigInt", None),
+        "real" => ("Float", Some(String::from("@db.Real"))),
+        "double precision" => ("Float", None),
+        "numeric" => ("Decimal", Some(with_arguments("Decimal"))),
+        "money" => ("Decimal", Some(String::from("@db.Money"))),
+        "character varying" => ("String", Some(with_arguments("VarChar"))),
+        "character" => ("String", Some(with_arguments("Char"))),
+        "text" => ("String", None),
+        "uuid" => ("String", Some(String::from("@db.Uuid"))),
+        "xml" => ("String", Some(String::from("@db.Xml"))),
+        "inet" => ("String", Some(String::from("@db.Inet"))),
+        "citext" => ("String", Some(String::from("@db.Citext"))),
+        "bit" => ("String", Some(with_arguments("Bit"))),
+        "bit varying" => ("String", Some(with_arguments("VarBit"))),
+        "boolean" => ("Boolean", None),
+        "timestamp without time zone" => ("DateTime", Some(with_arguments("Timestamp"))),
+        "timestamp with time zone" => ("DateTime", Some(with_arguments("Timestamptz"))),
+        "date" => ("DateTime", Some(String::from("@db.Date"))),
+        "time without time zone" => ("DateTime", Some(with_arguments("Time"))),
+        "time with time zone" => ("DateTime", Some(with_arguments("Timetz"))),
+        "json" => ("Json", Some(String::from("@db.Json"))),
+        "jsonb" => ("Json", None),
+        "bytea" => ("Bytes", None),
+        _ => return (format!("Unsupported(\"{}\")", db_type), None),
+    };
+    let mut field_type = field_type.to_string();
+    if is_array {
+        field_type.push_str("[]");
     }
-    fn format_db_annotation(&self) -> Option<String> {
-        let mut resp = String::from("@db.");
-        //  This feels bad
-        match self
-            .db_type_annotation
-            .clone()
-            .unwrap_or_else(|| "".to_string())
-            .as_str()
-        {
-            db_type if db_type.contains("tinyint") => resp.push_str("TinyInt"),
-            db_type if db_type.contains("smallint") && db_type.contains("unsigned") => resp
-                .push_str(
-                    db_type
-                        .replace("smallint unsigned", "UnsignedSmallInt")
-                        .as_str(),
-                ),
-            db_type if db_type.contains("smallint") => {
-                resp.push_str(db_type.replace("smallint", "SmallInt").as_str())
-            }
-            db_type if db_type.contains("mediumint") && db_type.contains("unsigned") => resp
-                .push_str(
-                    db_type
-                        .replace("mediumint unsigned", "UnsignedMediumInt")
-                        .as_str(),
-                ),
-            db_type if db_type.contains("mediumint") => {
-                resp.push_str(db_type.replace("mediumint", "MediumInt").as_str())
-            }
-            db_type if db_type.contains("bigint") && db_type.contains("unsigned") => resp.push_str(
-                db_type
-                    .replace("bigint unsigned", "UnsignedBigInt")
-                    .as_str(),
-            ),
-            db_type if db_type.contains("bigint") => {
-                resp.push_str(db_type.replace("bigint", "BigInt").as_str())
-            }
-            db_type if db_type.contains("int") && db_type.contains("unsigned") => {
-                resp.push_str(db_type.replace("int unsigned", "UnsignedInt").as_str())
-            }
-            db_type if db_type.contains("int") => {
-                resp.push_str(db_type.replace("int", "Int").as_str())
-            }
-            db_type if db_type.contains("float") => resp.push_str("Float"),
-            db_type if db_type.contains("double") => {
-                resp.push_str(db_type.replace("double", "Double").as_str())
-            }
-            db_type if db_type.contains("decimal") => {
-                resp.push_str(db_type.replace("decimal", "Decimal").as_str())
-            }
-            db_type if db_type.contains("datetime") => {
-                resp.push_str(db_type.replace("datetime", "DateTime").as_str())
-            }
-            db_type if db_type.contains("date") => {
-                resp.push_str(db_type.replace("date", "Date").as_str())
-            }
-            db_type if db_type.contains("time") => {
-                resp.push_str(db_type.replace("time", "Time").as_str())
-            }
-            db_type if db_type.contains("timestamp") => {
-                resp.push_str(db_type.replace("timestamp", "Timestamp").as_str())
-            }
-            db_type if db_type.contains("bool") => {
-                resp.push_str(db_type.replace("boolean", "Boolean").as_str())
-            }
-            db_type if db_type.contains("varchar") => {
-                resp.push_str(db_type.replace("varchar", "VarChar").as_str())
-            }
-            db_type if db_type.contains("mediumtext") => {
-                resp.push_str(db_type.replace("mediumtext", "MediumText").as_str())
-            }
-            db_type if db_type.contains("text") => {
-                resp.push_str(db_type.replace("text", "Text").as_str())
-            }
-            db_type if db_type.contains("json") => {
-                resp.push_str(db_type.replace("json", "Json").as_str())
-            }
-            db_type if db_type.contains("Text") => {
-                resp.push_str(db_type.replace("Text", "Text").as_str())
-            }
-            db_type if db_type.contains("Timestamp") => {
-                resp.push_str(db_type.replace("Timestamp", "Timestamp").as_str())
-            }
-            db_type if db_type.contains("VarChar") => {
-                resp.push_str(db_type.replace("VarChar", "VarChar").as_str())
-            }
-            db_type if db_type.contains("TinyInt") => {
-                resp.push_str(db_type.replace("TinyInt", "TinyInt").as_str())
-            }
-            db_type if db_type.contains("SmallInt") => {
-                resp.push_str(db_type.replace("SmallInt", "SmallInt").as_str())
-            }
-            db_type if db_type.contains("MediumInt") => {
-                resp.push_str(db_type.replace("MediumInt", "MediumInt").as_str())
-            }
-            db_type if db_type.contains("BigInt") => {
-                resp.push_str(db_type.replace("BigInt", "BigInt").as_str())
-            }
-            db_type if db_type.contains("Int") => {
-                resp.push_str(db_type.replace("Int", "Int").as_str())
-            }
-            db_type if db_type.contains("Float") => {
-                resp.push_str(db_type.replace("Float", "Float").as_str())
-            }
-            db_type if db_type.contains("Double") => {
-                resp.push_str(db_type.replace("Double", "Double").as_str())
-            }
-            db_type if db_type.contains("Decimal") => {
-                resp.push_str(db_type.replace("Decimal", "Decimal").as_str())
-            }
-            db_type if db_type.contains("DateTime") => {
-                resp.push_str(db_type.replace("DateTime", "DateTime").as_str())
-            }
-            db_type if db_type.contains("Date") => {
-                resp.push_str(db_type.replace("Date", "Date").as_str())
-            }
-            db_type if db_type.contains("Time") => {
-                resp.push_str(db_type.replace("Time", "Time").as_str())
-            }
-            db_type if db_type.contains("Boolean") => {
-                resp.push_str(db_type.replace("Boolean", "Boolean").as_str())
-            }
-            db_type if db_type.contains("Json") => {
-                resp.push_str(db_type.replace("Json", "Json").as_str())
-            }
-            db_type if db_type.contains("Binary") => {
-                resp.push_str(db_type.replace("Binary", "Binary").as_str())
-            }
-            db_type if db_type.contains("Blob") => {
-                resp.push_str(db_type.replace("Blob", "Blob").as_str())
-            }
-            db_type if db_type.contains("MediumBlob") => {
-                resp.push_str(db_type.replace("MediumBlob", "MediumBlob").as_str())
-            }
-            db_type if db_type.contains("LongBlob") => {
-                resp.push_str(db_type.replace("LongBlob", "LongBlob").as_str())
+    (field_type, annotation)
+}
+
+/// The Prisma type for a SQLite column type, following SQLite's own type affinity rules. SQLite
+/// has no native type annotations.
+fn sqlite_field_type(db_type: &str) -> String {
+    let db_type = db_type.to_uppercase();
+    String::from(match db_type.as_str() {
+        _ if db_type.contains("BOOL") => "Boolean",
+        _ if db_type.contains("DATE") || db_type.contains("TIME") => "DateTime",
+        _ if db_type.contains("BIGINT") => "BigInt",
+        _ if db_type.contains("INT") => "Int",
+        _ if db_type.contains("CHAR") || db_type.contains("CLOB") || db_type.contains("TEXT") => {
+            "String"
+        }
+        _ if db_type.contains("BLOB") || db_type.is_empty() => "Bytes",
+        _ if db_type.contains("REAL") || db_type.contains("FLOA") || db_type.contains("DOUB") => {
+            "Float"
+        }
+        _ if db_type.contains("JSON") => "Json",
+        _ => "Decimal",
+    })
+}
+
+/// Whether a default is the current time, such as `CURRENT_TIMESTAMP(3)` or `now()`.
+fn is_current_timestamp(default: &str) -> bool {
+    let default = default.to_uppercase();
+    let Some(precision) = ["CURRENT_TIMESTAMP", "LOCALTIMESTAMP", "NOW"]
+        .iter()
+        .find_map(|name| default.strip_prefix(name))
+    else {
+        return false;
+    };
+    precision.is_empty()
+        || precision
+            .strip_prefix('(')
+            .and_then(|precision| precision.strip_suffix(')'))
+            .is_some_and(|precision| precision.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Whether a default is a plain number such as `-3` or `10.50`, which Prisma takes as it is.
+fn is_number_literal(default: &str, allow_fraction: bool) -> bool {
+    let digits = default.strip_prefix('-').unwrap_or(default);
+    let (whole, fraction) = match digits.split_once('.') {
+        Some((whole, fraction)) if allow_fraction => (whole, fraction),
+        Some(_) => return false,
+        None => (digits, ""),
+    };
+    !whole.is_empty()
+        && whole.chars().all(|c| c.is_ascii_digit())
+        && fraction.chars().all(|c| c.is_ascii_digit())
+}
+
+impl Field {
+    /// Set the Prisma default from a column's default and extra as `DESCRIBE` shows them, the
+    /// way `prisma db pull` writes it. The field's type must already be set.
+    fn set_default(&mut self, default: Option<&str>, extra: &str) {
+        let extra = extra.to_lowercase();
+        self.is_updated_at = extra
+            .split("on update ")
+            .nth(1)
+            .is_some_and(is_current_timestamp);
+        if extra.contains("auto_increment") {
+            self.default = Some(String::from("autoincrement()"));
+            return;
+        }
+        let Some(default) = default else {
+            self.default = None;
+            return;
+        };
+        let quoted = format!("\"{}\"", default.replace('\\', "\\\\").replace('"', "\\\""));
+        self.default = Some(match self.field_type.trim_end_matches('?') {
+            _ if is_current_timestamp(default) => String::from("now()"),
+            // An expression default is kept in its parentheses, as `prisma db pull` writes it.
+            _ if extra.contains("default_generated") => match default.starts_with('(') {
+                true => format!("dbgenerated({})", quoted),
+                false => format!("dbgenerated(\"({})\")", &quoted[1..quoted.len() - 1]),
+            },
+            "Boolean" if default == "1" || default.eq_ignore_ascii_case("true") => {
+                String::from("true")
             }
-            db_type if db_type.contains("TinyBlob") => {
-                resp.push_str(db_type.replace("TinyBlob", "TinyBlob").as_str())
+            "Boolean" if default == "0" || default.eq_ignore_ascii_case("false") => {
+                String::from("false")
             }
+            "String" | "DateTime" | "Json" => quoted,
+            "Int" | "BigInt" if is_number_literal(default, false) => default.to_string(),
+            "Float" | "Decimal" if is_number_literal(default, true) => default.to_string(),
+            // Anything else has no Prisma literal, so it's left to the database.
+            _ => format!("dbgenerated({})", quoted),
+        });
+    }
 
-            _ => return None,
+    /// Point the field at an enum, translating its default to the enum value's name.
+    pub fn set_enum(&mut self, enumeration: &Enum) {
+        let mut field_type = enumeration.get_name().to_string();
+        if !self.is_required {
+            field_type.push('?');
         }
-        Some(resp)
+        self.set_field_type(field_type);
+        self.db_type_annotation = None;
+        self.default = self.default.as_deref().map(|default| {
+            enumeration
+                .find_value(default)
+                .map(|value| value.name.clone())
+                .unwrap_or_else(|| default.to_string())
+        });
+        self.set_field_type_length(self.field_type.len());
     }
-}
 
-impl From<Description> for Field {
-    fn from(description: Description) -> Self {
+    /// Build a field from a column, reading its type in the database's dialect.
+    pub fn from_description(description: Description, dialect: Dialect) -> Field {
+        let (field_type, annotation) = match dialect {
+            Dialect::MySql => return Field::from(description),
+            Dialect::Postgres => postgres_field_type(&description.type_),
+            Dialect::Sqlite => (sqlite_field_type(&description.type_), None),
+        };
+        let is_array = field_type.ends_with("[]");
+        let mut field = Field::from_column(description, field_type, annotation);
+        field.is_array = is_array;
+        field
+    }
+
+    /// Build a field from a column, given the Prisma type and annotation its type maps to.
+    fn from_column(
+        description: Description,
+        mut field_type: String,
+        annotation: Option<String>,
+    ) -> Field {
         let mut field = Field::new();
+        field.documentation = documentation_lines(description.comment.as_deref());
         field.set_name(description.field);
         field.set_is_id(description.key.contains("PRI"));
         field.set_is_required(description.null == "NO");
-        field.set_unique(match description.key.contains("PRI") {
+        // The primary key is already unique, marking it @unique would add a second index.
+        field.set_unique(match description.key.contains("UNI") && !field.is_id {
             true => Some(UniqueFlag { map: None }),
-            false => match description.key.contains("UNI") {
-                true => Some(UniqueFlag { map: None }),
-                false => None,
-            },
+            false => None,
         });
-        field.set_field_type(field.get_field_type_from_db_type());
-        field.set_db_type_annotation(description.type_.clone());
-        field.default = match description.default {
-            Some(ref default) => match default.as_str() {
-                "CURRENT_TIMESTAMP" => Some("now()".to_string()),
-                "1" => match field.field_type.as_str() {
-                    "Boolean" => Some("true".to_string()),
-                    _ => Some(default.clone()),
-                },
-                "0" => match field.field_type.as_str() {
-                    "Boolean" => Some("false".to_string()),
-                    _ => Some(default.clone()),
-                },
-                _ => Some(default.clone()),
-            },
-            None => None,
-        };
+        if !field.is_required && !field_type.ends_with("[]") {
+            field_type.push('?');
+        }
+        field.set_field_type(field_type);
+        field.db_type_annotation = annotation;
+        field.set_default(description.default.as_deref(), &description.extra);
         field.set_target_name_length(field.name.len());
         field.set_field_type_length(field.field_type.len());
         field
     }
 }
 
+impl From<Description> for Field {
+    fn from(description: Description) -> Self {
+        let column_type = description.column_type();
+        let (field_type, annotation) = mysql_field_type(&column_type);
+        let default = description.default.clone();
+        let mut field = Field::from_column(description, field_type, annotation);
+        // The enum value's name is looked up from the raw default once the enum is set.
+        if let ColumnType::Enum { .. } = column_type {
+            field.default = default;
+        }
+        field
+    }
+}
+
 impl PartialEq for Field {
     fn eq(&self, other: &Self) -> bool {
-        let resp = self.name == other.name
+        self.name == other.name
             && self.field_type == other.field_type
             && self.is_id == other.is_id
             && self.is_required == other.is_required
             && self.unique == other.unique
             && self.default == other.default
+            && self.is_updated_at == other.is_updated_at
             && self.db_type_annotation == other.db_type_annotation
-            && self.relation == other.relation;
-        resp
+            && self.relation == other.relation
+            && self.map == other.map
+            && self.documentation == other.documentation
     }
 }
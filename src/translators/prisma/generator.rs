@@ -1,9 +1,23 @@
+use crate::translators::prisma::options::{config_block, string_list};
+use crate::translators::prisma::parser::Block;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct Generator {
-    name: String,
-    provider: String,
+    pub name: String,
+    pub provider: String,
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default)]
+    pub preview_features: Vec<String>,
+    #[serde(default)]
+    pub binary_targets: Vec<String>,
+}
+
+impl Default for Generator {
+    fn default() -> Generator {
+        Generator::new()
+    }
 }
 
 impl Generator {
@@ -11,24 +25,43 @@ impl Generator {
         Generator {
             name: String::from("client"),
             provider: String::from("prisma-client-js"),
+            output: None,
+            preview_features: vec![],
+            binary_targets: vec![],
         }
     }
-    pub fn parse_from_disk(generator_str: &String) -> Generator {
-        let pieces: Vec<&str> = generator_str.split(" = ").collect();
-        let provider_piece = pieces[1];
-        let provider_pieces: Vec<&str> = provider_piece.split(" ").collect();
-        let binding = String::from(provider_pieces[0].replace('"', "").replace("\n", ""));
-        let provider_dirty: Vec<&str> = binding.split("}").collect();
-        let provider = String::from(provider_dirty[0]);
+    pub fn from_block(block: &Block) -> Generator {
+        let string = |name: &str| {
+            block
+                .property(name)
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+        };
+        let strings = |name: &str| {
+            block
+                .property(name)
+                .and_then(|value| value.as_strings())
+                .unwrap_or_default()
+        };
         Generator {
-            name: String::from("client"),
-            provider,
+            name: block.name.clone(),
+            provider: string("provider").unwrap_or_default(),
+            output: string("output"),
+            preview_features: strings("previewFeatures"),
+            binary_targets: strings("binaryTargets"),
         }
     }
     pub fn as_text(&self) -> String {
-        format!(
-            "generator {0} {{\n  provider = \"{1}\"\n}}",
-            self.name, self.provider
-        )
+        let mut properties = vec![("provider", format!("\"{}\"", self.provider))];
+        if let Some(output) = &self.output {
+            properties.push(("output", format!("\"{}\"", output)));
+        }
+        if !self.preview_features.is_empty() {
+            properties.push(("previewFeatures", string_list(&self.preview_features)));
+        }
+        if !self.binary_targets.is_empty() {
+            properties.push(("binaryTargets", string_list(&self.binary_targets)));
+        }
+        config_block("generator", &self.name, &properties)
     }
 }
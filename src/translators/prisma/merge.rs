@@ -0,0 +1,542 @@
+use crate::translators::prisma::field::documentation_text;
+use crate::translators::prisma::model::{base_type, Model};
+use crate::translators::prisma::options::ModelGroup;
+use crate::translators::prisma::parser::{
+    Attribute, Block, BlockKind, BlockPart, FieldDeclaration, ParseError, SchemaFile,
+};
+use crate::translators::prisma::schema::{model_file_name, PrismaSchema, ENUMS_FILE, SCHEMA_FILE};
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+
+/// Attributes whose value comes from the database. Any others on a field, such as @updatedAt or
+/// @ignore, were written by hand and are kept.
+const DATABASE_ATTRIBUTES: [&str; 6] = ["id", "unique", "default", "map", "db", "relation"];
+
+/// Block attributes whose value comes from the database.
+const DATABASE_DIRECTIVES: [&str; 4] = ["id", "unique", "index", "map"];
+
+/// The name of an attribute, with db.X reduced to db.
+fn attribute_name(attribute: &Attribute) -> &str {
+    match attribute.name.starts_with("db.") {
+        true => "db",
+        false => &attribute.name,
+    }
+}
+
+/// The `fields: [..]` of a field's relation, if it has one.
+fn relation_fields(field: &FieldDeclaration) -> Option<Vec<String>> {
+    field
+        .attribute("relation")?
+        .argument(Some("fields"))?
+        .as_names()
+}
+
+/// The name of a field's relation, if it has one.
+fn relation_name(field: &FieldDeclaration) -> Option<&str> {
+    let relation = field.attribute("relation")?;
+    relation
+        .argument(None)
+        .or_else(|| relation.argument(Some("name")))?
+        .as_str()
+}
+
+/// Whether a relation field on disk is the database's relation field, by the model it points at
+/// and the columns it's over. Back relations have no columns, so ambiguous ones go by name.
+fn is_same_relation(disk: &FieldDeclaration, database: &FieldDeclaration) -> bool {
+    disk.field_type.name == database.field_type.name
+        && relation_fields(disk) == relation_fields(database)
+        && (relation_fields(database).is_some()
+            || relation_name(database).is_none()
+            || relation_name(disk) == relation_name(database))
+}
+
+/// A field as it's written in a merged model.
+struct FieldLine {
+    name: String,
+    field_type: String,
+    attributes: Vec<String>,
+    comment: Option<String>,
+    documentation: Vec<String>,
+}
+
+impl FieldLine {
+    fn from_declaration(declaration: &FieldDeclaration) -> FieldLine {
+        FieldLine {
+            name: declaration.name.clone(),
+            field_type: declaration.field_type.as_text(),
+            attributes: declaration
+                .attributes
+                .iter()
+                .map(|attribute| attribute.as_text())
+                .collect(),
+            comment: declaration.comment.clone(),
+            documentation: declaration.documentation.clone(),
+        }
+    }
+
+    /// The database's version of the field, keeping the hand-written attributes, documentation
+    /// and comment on disk.
+    fn merged(disk: &FieldDeclaration, database: &FieldDeclaration) -> FieldLine {
+        let mut field = FieldLine::from_declaration(database);
+        let database_names: Vec<&str> = database.attributes.iter().map(attribute_name).collect();
+        for attribute in disk.attributes.iter() {
+            let name = attribute_name(attribute);
+            if database_names.contains(&name) {
+                continue;
+            }
+            // A default the database doesn't know about, such as cuid(), is set by Prisma.
+            if !DATABASE_ATTRIBUTES.contains(&name) || name == "default" {
+                field.attributes.push(attribute.as_text());
+            }
+        }
+        field.comment = disk.comment.clone();
+        field.documentation = disk.documentation.clone();
+        field
+    }
+}
+
+/// A line in a merged model body, either a field or a line kept as it was.
+enum BodyLine {
+    Field(FieldLine),
+    Verbatim(String),
+}
+
+/// Aligns the names and types of the field lines the way `prisma format` does.
+fn format_body(lines: &[BodyLine], directives: &[String]) -> Vec<String> {
+    let fields = lines.iter().filter_map(|line| match line {
+        BodyLine::Field(field) => Some(field),
+        BodyLine::Verbatim(_) => None,
+    });
+    let name_width = fields.clone().map(|f| f.name.len()).max().unwrap_or(0);
+    let type_width = fields.map(|f| f.field_type.len()).max().unwrap_or(0);
+    let mut body: Vec<String> = vec![];
+    for line in lines {
+        match line {
+            BodyLine::Verbatim(line) => body.push(line.clone()),
+            BodyLine::Field(field) => {
+                body.extend(
+                    documentation_text(&field.documentation, "  ")
+                        .lines()
+                        .map(str::to_string),
+                );
+                let mut text = format!(
+                    "  {:name_width$} {:type_width$} {}",
+                    field.name,
+                    field.field_type,
+                    field.attributes.join(" ")
+                );
+                if let Some(comment) = &field.comment {
+                    text = format!("{} {}", text.trim_end(), comment);
+                }
+                body.push(text.trim_end().to_string());
+            }
+        }
+    }
+    while body.last().is_some_and(|line| line.trim().is_empty()) {
+        body.pop();
+    }
+    if !directives.is_empty() {
+        body.push(String::new());
+        body.extend(directives.iter().cloned());
+    }
+    body
+}
+
+/// The whole lines in the text between two parts of a block. The first piece finishes the line
+/// of the part before, and the last starts the line of the part after.
+fn trivia_lines(trivia: &str) -> Vec<String> {
+    let pieces: Vec<&str> = trivia.split('\n').collect();
+    match pieces.len() {
+        0..=2 => vec![],
+        length => pieces[1..length - 1]
+            .iter()
+            .map(|line| line.trim_end().to_string())
+            .collect(),
+    }
+}
+
+/// Applies the database's version of a model to the model's block on disk, keeping its
+/// documentation, comments, field order, hand-written attributes and relation fields the
+/// database still has.
+fn merge_model(
+    file: &SchemaFile,
+    block: &Block,
+    database: &Block,
+    directives: &[String],
+    model_names: &HashSet<String>,
+) -> String {
+    let is_relation = |field: &FieldDeclaration| model_names.contains(&field.field_type.name);
+    let mut header = String::new();
+    let mut lines: Vec<BodyLine> = vec![];
+    let mut kept_directives: Vec<String> = vec![];
+    let mut in_directives = false;
+    let mut after_header = true;
+    // The database's field each field on disk became, by the name it's written with.
+    let mut written: HashMap<String, String> = HashMap::new();
+    for part in file.block_parts(block) {
+        match part {
+            BlockPart::Header(text) => header = text,
+            BlockPart::Trivia(text) => {
+                if after_header {
+                    // A comment on the same line as the opening brace stays there.
+                    header.push_str(text.split('\n').next().unwrap_or("").trim_end());
+                    after_header = false;
+                }
+                for line in trivia_lines(&text) {
+                    // Comments between directives belong with the directives.
+                    match in_directives {
+                        true if !line.trim().is_empty() => kept_directives.push(line),
+                        true => {}
+                        false => lines.push(BodyLine::Verbatim(line)),
+                    }
+                }
+            }
+            BlockPart::Attribute(attribute) => {
+                in_directives = true;
+                if !DATABASE_DIRECTIVES.contains(&attribute.name.as_str()) {
+                    kept_directives.push(file.attribute_text(attribute).trim_end().to_string());
+                }
+            }
+            BlockPart::Field(field) => {
+                // A relation field may have been renamed by hand, so it's matched by what it
+                // points at and kept as it is. Without a match, its foreign key was dropped.
+                let database_field = match is_relation(field) {
+                    true => database.fields.iter().find(|database_field| {
+                        is_relation(database_field)
+                            && !written.contains_key(&database_field.name)
+                            && is_same_relation(field, database_field)
+                    }),
+                    false => database.fields.iter().find(|d| d.name == field.name),
+                };
+                // Fields the database doesn't have were dropped along with their column, and
+                // their documentation with them.
+                let Some(database_field) = database_field else {
+                    continue;
+                };
+                written.insert(database_field.name.clone(), field.name.clone());
+                lines.push(BodyLine::Field(match is_relation(field) {
+                    true => FieldLine::from_declaration(field),
+                    false => FieldLine::merged(field, database_field),
+                }));
+            }
+        }
+    }
+    // Fields the disk doesn't have yet go after the field before them in the database.
+    let mut previous: Option<&str> = None;
+    for database_field in database.fields.iter() {
+        if !written.contains_key(&database_field.name) {
+            let position = previous
+                .and_then(|previous| {
+                    lines.iter().position(
+                        |line| matches!(line, BodyLine::Field(field) if field.name == previous),
+                    )
+                })
+                .map(|position| position + 1)
+                .unwrap_or_else(|| {
+                    lines
+                        .iter()
+                        .position(|line| matches!(line, BodyLine::Field(_)))
+                        .unwrap_or(lines.len())
+                });
+            // A new field brings its documentation with it, while the disk's is kept for the
+            // rest.
+            lines.insert(
+                position,
+                BodyLine::Field(FieldLine::from_declaration(database_field)),
+            );
+            written.insert(database_field.name.clone(), database_field.name.clone());
+        }
+        previous = written.get(&database_field.name).map(String::as_str);
+    }
+    let mut directives: Vec<String> = directives
+        .iter()
+        .map(|directive| format!("  {}", directive))
+        .collect();
+    directives.extend(kept_directives);
+    let mut text = header;
+    text.push('\n');
+    for line in format_body(&lines, &directives) {
+        text.push_str(&line);
+        text.push('\n');
+    }
+    text.push('}');
+    text
+}
+
+/// What a file of the schema on disk is merged with, besides the blocks already in it.
+struct Placement<'a> {
+    /// The whole database schema, which models and enums are merged with wherever they are on
+    /// disk.
+    database: &'a PrismaSchema,
+    /// The database's models and enums, parsed from their text.
+    database_file: &'a SchemaFile,
+    /// The models and enums the database's schema puts in this file.
+    part: &'a PrismaSchema,
+    /// The models and enums on disk in any file, which aren't added again.
+    on_disk: &'a HashSet<String>,
+    /// The types the models refer to once merged, in any file.
+    referenced: &'a HashSet<String>,
+    /// Whether a model or enum the database doesn't have is removed from this file.
+    owns: &'a dyn Fn(&Block) -> bool,
+}
+
+/// Removes what's written directly above a dropped block: the comments on the lines before it,
+/// and the blank line separating it from the block before.
+fn drop_leading_lines(text: &mut String) {
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    lines.pop();
+    while lines
+        .last()
+        .is_some_and(|line| line.trim_start().starts_with("//"))
+    {
+        lines.pop();
+    }
+    if lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let mut kept = lines.join("\n");
+    if !kept.is_empty() {
+        kept.push('\n');
+    }
+    *text = kept;
+}
+
+/// Merges the database's blocks into one file of the schema on disk. Generator and datasource
+/// blocks, comments and blocks the file keeps are written back exactly as they were.
+fn merge_file(file: &SchemaFile, placement: &Placement) -> String {
+    let database_block = |kind: BlockKind, name: &str| {
+        placement
+            .database_file
+            .blocks
+            .iter()
+            .find(|block| block.kind == kind && block.name == name)
+    };
+    let model_names: HashSet<String> = placement
+        .database
+        .get_models()
+        .iter()
+        .map(|model| model.get_name().to_string())
+        .collect();
+    let trivia = file.trivia();
+    let mut text = String::new();
+    let mut last_model_end: Option<usize> = None;
+    let mut dropped = false;
+    for (block, before) in file.blocks.iter().zip(trivia.iter()) {
+        let before = match dropped {
+            // The line the dropped block ended on goes with it.
+            true if text.is_empty() => before.trim_start_matches('\n'),
+            true => before.strip_prefix('\n').unwrap_or(before),
+            false => before,
+        };
+        text.push_str(before);
+        dropped = false;
+        match block.kind {
+            BlockKind::Model => match database_block(BlockKind::Model, &block.name) {
+                Some(database) => {
+                    let model = placement
+                        .database
+                        .get_models()
+                        .iter()
+                        .find(|model| model.get_name() == block.name)
+                        .expect("parsed model to be in the schema");
+                    text.push_str(&merge_model(
+                        file,
+                        block,
+                        database,
+                        &model.directive_lines(),
+                        &model_names,
+                    ));
+                    last_model_end = Some(text.len());
+                }
+                None if (placement.owns)(block) => dropped = true,
+                None => {
+                    text.push_str(&file.block_text(block));
+                    last_model_end = Some(text.len());
+                }
+            },
+            BlockKind::Enum => {
+                match placement
+                    .database
+                    .get_enums()
+                    .iter()
+                    .find(|enumeration| enumeration.get_name() == block.name)
+                {
+                    Some(enumeration) => {
+                        text.push_str(&documentation_text(&block.documentation, ""));
+                        text.push_str(enumeration.as_text().trim_end());
+                    }
+                    // Enums that nothing refers to anymore belonged to a dropped column.
+                    None if (placement.owns)(block)
+                        && !placement.referenced.contains(&block.name) =>
+                    {
+                        dropped = true
+                    }
+                    None => text.push_str(&file.block_text(block)),
+                }
+            }
+            _ => text.push_str(&file.block_text(block)),
+        }
+        if dropped {
+            drop_leading_lines(&mut text);
+        }
+    }
+    let after = trivia.last().map(String::as_str).unwrap_or("");
+    match dropped {
+        true => text.push_str(after.strip_prefix('\n').unwrap_or(after)),
+        false => text.push_str(after),
+    }
+    // New models go after the last model on disk, new enums at the end.
+    let new_models: Vec<String> = placement
+        .part
+        .get_models()
+        .iter()
+        .filter(|model| !placement.on_disk.contains(model.get_name()))
+        .map(|model| model.as_text().trim_end().to_string())
+        .collect();
+    if !new_models.is_empty() {
+        let position = last_model_end.unwrap_or(text.trim_end().len());
+        let mut inserted = new_models.join("\n\n");
+        if position > 0 {
+            inserted.insert_str(0, "\n\n");
+        }
+        text.insert_str(position, &inserted);
+    }
+    for enumeration in placement.part.get_enums() {
+        if placement.on_disk.contains(enumeration.get_name()) {
+            continue;
+        }
+        text.truncate(text.trim_end().len());
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        text.push_str(enumeration.as_text().trim_end());
+    }
+    let mut text = text.trim_end().to_string();
+    text.push('\n');
+    text
+}
+
+/// The names of the models and enums in the files.
+fn block_names(files: &[&SchemaFile]) -> HashSet<String> {
+    files
+        .iter()
+        .flat_map(|file| file.blocks.iter())
+        .filter(|block| matches!(block.kind, BlockKind::Model | BlockKind::Enum))
+        .map(|block| block.name.clone())
+        .collect()
+}
+
+/// The types the database's models refer to.
+fn database_types(database: &PrismaSchema) -> HashSet<String> {
+    database
+        .get_models()
+        .iter()
+        .flat_map(|model| model.get_fields().iter())
+        .map(|field| base_type(&field.field_type).to_string())
+        .collect()
+}
+
+/// Merges the database's schema into the schema text on disk. Generator and datasource blocks,
+/// comments, field order, relation fields and hand-written attributes on disk are kept, while
+/// models, fields, enums and the database's attributes follow the database.
+pub fn merge(disk_text: &str, database: &PrismaSchema) -> Result<String, ParseError> {
+    let file = SchemaFile::parse(disk_text)?;
+    let database_file = SchemaFile::parse(&database.models_text())?;
+    Ok(merge_file(
+        &file,
+        &Placement {
+            database,
+            database_file: &database_file,
+            part: database,
+            on_disk: &block_names(&[&file]),
+            referenced: &database_types(database),
+            owns: &|_| true,
+        },
+    ))
+}
+
+/// Merges the database's schema into the files of a schema folder, given by name with their
+/// text. Models and enums are merged wherever they are on disk and new ones go in the file the
+/// database's split puts them in. One the database no longer has is only removed from that same
+/// file, so blocks written by hand elsewhere stay. Returns the files that changed or are new.
+pub fn merge_folder(
+    files: &[(String, String)],
+    database: &PrismaSchema,
+    groups: &[ModelGroup],
+) -> Result<Vec<(String, String)>> {
+    let parsed = files
+        .iter()
+        .map(|(file_name, text)| {
+            SchemaFile::parse(text).with_context(|| format!("couldn't parse {}", file_name))
+        })
+        .collect::<Result<Vec<SchemaFile>>>()?;
+    let database_file = SchemaFile::parse(&database.models_text())?;
+    let owns = |file_name: &str, block: &Block| match block.kind {
+        BlockKind::Model => model_file_name(&Model::from_block(block), groups) == file_name,
+        BlockKind::Enum => file_name == ENUMS_FILE,
+        _ => false,
+    };
+    let in_database = |block: &Block| {
+        database
+            .get_models()
+            .iter()
+            .any(|model| model.get_name() == block.name)
+    };
+    // Models kept outside the file they'd be written to still use their enums.
+    let mut referenced = database_types(database);
+    for ((file_name, _), file) in files.iter().zip(parsed.iter()) {
+        for block in file.blocks.iter() {
+            if block.kind == BlockKind::Model && !in_database(block) && !owns(file_name, block) {
+                referenced.extend(
+                    block
+                        .fields
+                        .iter()
+                        .map(|field| field.field_type.name.clone()),
+                );
+            }
+        }
+    }
+    let on_disk = block_names(&parsed.iter().collect::<Vec<&SchemaFile>>());
+    let split = database.split(groups);
+    let empty = PrismaSchema::new();
+    let part = |file_name: &str| {
+        split
+            .iter()
+            .find(|(name, _)| name == file_name)
+            .map(|(_, part)| part)
+            .unwrap_or(&empty)
+    };
+    let merge_into = |file_name: &str, file: &SchemaFile| {
+        merge_file(
+            file,
+            &Placement {
+                database,
+                database_file: &database_file,
+                part: part(file_name),
+                on_disk: &on_disk,
+                referenced: &referenced,
+                owns: &|block| owns(file_name, block),
+            },
+        )
+    };
+    let mut written: Vec<(String, String)> = vec![];
+    for ((file_name, text), file) in files.iter().zip(parsed.iter()) {
+        let merged = merge_into(file_name, file);
+        if &merged != text {
+            written.push((file_name.clone(), merged));
+        }
+    }
+    for (file_name, part) in split.iter() {
+        if files.iter().any(|(name, _)| name == file_name) {
+            continue;
+        }
+        let text = match file_name == SCHEMA_FILE {
+            true => part.config_text(),
+            false => merge_into(file_name, &SchemaFile::parse("")?),
+        };
+        if !text.trim().is_empty() {
+            written.push((file_name.clone(), text));
+        }
+    }
+    Ok(written)
+}
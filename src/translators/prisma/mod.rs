@@ -1,7 +1,14 @@
 pub mod data_source;
+pub mod diff;
+pub mod enumeration;
 pub mod field;
 pub mod generator;
+pub mod merge;
 pub mod model;
+pub mod naming;
+pub mod options;
+pub mod parser;
 pub mod relation;
 pub mod schema;
+pub mod tables;
 pub mod unique_flag;
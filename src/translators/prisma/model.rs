@@ -1,6 +1,11 @@
-use crate::remotes::sql::Table;
-use crate::translators::prisma::field::Field;
+use crate::remotes::sql::{IndexKey, Key, Table};
+use crate::translators::prisma::enumeration::{enum_name, Enum};
+use crate::translators::prisma::field::{documentation_lines, documentation_text, Field};
+use crate::translators::prisma::naming::NamingPolicy;
+use crate::translators::prisma::parser::Block;
+use crate::translators::prisma::unique_flag::UniqueFlag;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 // use std::process::exit;
 
 #[derive(Deserialize, Serialize, Debug, Clone, Eq)]
@@ -10,6 +15,9 @@ pub struct Model {
     directives: Vec<String>,
     name_column_width: usize,
     field_type_column_width: usize,
+    /// The `///` lines written above the model, from the table's comment.
+    #[serde(default)]
+    documentation: Vec<String>,
 }
 
 impl Model {
@@ -20,13 +28,15 @@ impl Model {
             directives: vec![],
             name_column_width: 0,
             field_type_column_width: 0,
+            documentation: vec![],
         }
     }
 
     pub fn as_text(&self) -> String {
-        let mut text = String::new();
+        let mut text = documentation_text(&self.documentation, "");
         text.push_str(&format!("model {} {{\n", self.name));
         for field in &self.fields {
+            text.push_str(&documentation_text(&field.documentation, "  "));
             text.push_str("  ");
             text.push_str(
                 field
@@ -37,19 +47,45 @@ impl Model {
                     )
                     .as_str(),
             );
-            text.push_str("\n");
+            text.push('\n');
         }
-        if self.directives.len() > 0 {
+        if !self.directives.is_empty() {
             for directive in &self.directives {
-                text.push_str("\n");
+                text.push('\n');
                 text.push_str(directive.as_str());
             }
-            text.push_str("\n");
+            text.push('\n');
         }
         text.push_str("}\n");
         text
     }
 
+    /// Each field as it would be written, without the padding that aligns it with the others.
+    pub fn field_lines(&self) -> Vec<String> {
+        let number_of_id_fields = self.get_number_of_id_fields();
+        self.fields
+            .iter()
+            .map(|field| {
+                field
+                    .as_text(
+                        field.name.len(),
+                        field.field_type.len(),
+                        number_of_id_fields,
+                    )
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    /// Each @@ directive as it would be written, without indentation.
+    pub fn directive_lines(&self) -> Vec<String> {
+        self.directives
+            .iter()
+            .map(|directive| directive.trim().to_string())
+            .collect()
+    }
+
     fn get_number_of_id_fields(&self) -> usize {
         let mut count = 0;
         for field in &self.fields {
@@ -60,61 +96,179 @@ impl Model {
         count
     }
 
-    pub fn parse_from_disk(model_str: &str) -> Option<Model> {
-        let mut name = String::new();
-        let mut pieces: Vec<&str> = model_str.split("{").collect();
-        let mut directives: Vec<String> = vec![];
-        name.push_str(pieces.remove(0).replace(" ", "").as_str());
-        if name.len() == 0 {
-            return None;
+    /// Build a model from its block in a schema file.
+    pub fn from_block(block: &Block) -> Model {
+        let mut model = Model::new();
+        model.set_name(block.name.clone());
+        model.documentation = block.documentation.clone();
+        for declaration in block.fields.iter() {
+            model.add_field(Field::from_declaration(declaration));
         }
-        assert!(pieces.len() == 1);
-        let field_strs = pieces.pop().expect("pieces to have a value").split("\n");
-        let mut fields: Vec<Field> = vec![];
-        let mut name_column_width = 0;
-        let mut field_type_column_width = 0;
-        for field_str in field_strs {
-            if field_str.len() == 0 {
-                continue;
-            }
-            if field_str.trim().starts_with("@@") {
-                directives.push(field_str.to_string());
-                continue;
-            }
-            let field = Field::parse_from_disk(field_str.trim());
-            if field.is_none() {
-                continue;
-            } else {
-                if field.as_ref().unwrap().name.len() + 1 > name_column_width {
-                    name_column_width = field.as_ref().unwrap().name.len() + 1;
+        for attribute in block.attributes.iter() {
+            model.add_directive(attribute.as_text());
+        }
+        model.set_column_widths();
+        model
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name of the table behind the model, from its @@map if it has one.
+    pub fn table_name(&self) -> &str {
+        self.directives
+            .iter()
+            .find_map(|directive| {
+                directive
+                    .trim()
+                    .strip_prefix("@@map(\"")?
+                    .strip_suffix("\")")
+            })
+            .unwrap_or(&self.name)
+    }
+
+    pub fn get_documentation(&self) -> &[String] {
+        &self.documentation
+    }
+
+    pub fn get_fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Whether a field with this name already exists on the model.
+    pub fn has_field(&self, name: &str) -> bool {
+        self.fields.iter().any(|field| field.name == name)
+    }
+
+    fn add_field(&mut self, field: Field) {
+        self.fields.push(field);
+    }
+
+    /// Push a relation field onto the end of the model, widening the columns to fit it.
+    pub fn add_relation_field(&mut self, field: Field) {
+        self.add_field(field);
+        self.set_column_widths();
+    }
+
+    fn add_directive(&mut self, directive: String) {
+        self.directives.push(format!("  {}", directive));
+    }
+
+    /// Adds @@unique and @@index directives for the table's keys. Single column unique keys are
+    /// put on the field itself so that the constraint name can be mapped.
+    fn add_key_directives(&mut self, table: &Table) {
+        let mut unique_directives: Vec<String> = vec![];
+        let mut index_directives: Vec<String> = vec![];
+        for key in table.keys.keys.iter() {
+            match key {
+                Key::Unique(unique_keys) => {
+                    for unique_key in unique_keys {
+                        if unique_key.column_names.len() == 1 {
+                            if let Some(field) = self
+                                .fields
+                                .iter_mut()
+                                .find(|field| field.name == unique_key.column_names[0])
+                            {
+                                field.unique = Some(UniqueFlag {
+                                    map: Some(unique_key.constraint_name.clone()),
+                                });
+                            }
+                            continue;
+                        }
+                        unique_directives.push(format!(
+                            "@@unique([{}], map: \"{}\")",
+                            unique_key.column_names.join(", "),
+                            unique_key.constraint_name
+                        ));
+                    }
                 }
-                if field.as_ref().unwrap().field_type.len() + 1 > field_type_column_width {
-                    field_type_column_width = field.as_ref().unwrap().field_type.len() + 1;
+                Key::Index(index_key) => {
+                    index_directives.push(index_directive(std::slice::from_ref(index_key)))
                 }
-                fields.push(field.unwrap());
+                Key::MultiIndex(index_keys) => index_directives.push(index_directive(index_keys)),
+                Key::Foreign(_) => {}
             }
         }
-
-        for field in fields.iter_mut() {
-            field.set_target_name_length(name_column_width);
-            field.set_field_type_length(field_type_column_width);
+        for directive in unique_directives.into_iter().chain(index_directives) {
+            self.add_directive(directive);
         }
+    }
 
-        Some(Model {
-            name,
-            fields,
-            directives,
-            name_column_width,
-            field_type_column_width,
-        })
+    /// The names the model's column fields would take under the naming policy, by current name.
+    pub fn field_renames(
+        &self,
+        naming: NamingPolicy,
+        model_names: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        self.fields
+            .iter()
+            .filter(|field| !model_names.contains_key(base_type(&field.field_type)))
+            .map(|field| (field.name.clone(), naming.field_name(&field.name)))
+            .collect()
     }
 
-    fn set_name(&mut self, name: String) {
-        self.name = name;
+    /// Renames the model and its fields following the naming policy, mapping each renamed one
+    /// back to its database name. Relation fields named after a model follow that model's new
+    /// name, and the columns in relations and directives follow their fields.
+    pub fn apply_naming(
+        &mut self,
+        naming: NamingPolicy,
+        model_names: &HashMap<String, String>,
+        field_renames: &HashMap<String, HashMap<String, String>>,
+    ) {
+        let no_renames = HashMap::new();
+        let own_renames = field_renames.get(&self.name).unwrap_or(&no_renames);
+        for field in self.fields.iter_mut() {
+            let base = base_type(&field.field_type).to_string();
+            let Some(model_name) = model_names.get(&base) else {
+                if let Some(renamed) = own_renames.get(&field.name) {
+                    if renamed != &field.name {
+                        field.map = Some(field.name.clone());
+                        field.name = renamed.clone();
+                    }
+                }
+                continue;
+            };
+            field.name = if field.name == base && !field.is_array {
+                naming.field_name(model_name)
+            } else {
+                naming.field_name(&field.name)
+            };
+            field.field_type = field.field_type.replacen(&base, model_name, 1);
+            if let Some(relation) = field.relation.as_mut() {
+                relation
+                    .rename_columns(own_renames, field_renames.get(&base).unwrap_or(&no_renames));
+            }
+        }
+        for directive in self.directives.iter_mut() {
+            *directive = rename_directive_columns(directive, own_renames);
+        }
+        let model_name = model_names
+            .get(&self.name)
+            .cloned()
+            .unwrap_or_else(|| self.name.clone());
+        if model_name != self.name {
+            let map = format!("@@map(\"{}\")", self.name);
+            self.add_directive(map);
+            self.name = model_name;
+        }
+        self.set_column_widths();
     }
 
-    fn add_field(&mut self, field: Field) {
-        self.fields.push(field);
+    fn set_column_widths(&mut self) {
+        // A model can be written without any fields, such as `model draft {}`.
+        self.name_column_width = self.fields.iter().map(|f| f.name.len()).max().unwrap_or(0);
+        self.field_type_column_width = self
+            .fields
+            .iter()
+            .map(|f| f.field_type.len())
+            .max()
+            .unwrap_or(0);
     }
 }
 
@@ -122,8 +276,14 @@ impl From<&Table> for Model {
     fn from(table: &Table) -> Self {
         let mut model = Model::new();
         model.set_name(table.name.clone());
+        model.documentation = documentation_lines(table.comment.as_deref());
+        let enums = Enum::from_table(table);
         for description in table.description.clone().into_iter() {
-            let field = Field::from(description);
+            let mut field = Field::from_description(description, table.dialect);
+            let enum_name = enum_name(&table.name, &field.name);
+            if let Some(enumeration) = enums.iter().find(|e| e.get_name() == enum_name) {
+                field.set_enum(enumeration);
+            }
             model.add_field(field);
         }
         if model.get_number_of_id_fields() > 1 {
@@ -144,60 +304,68 @@ impl From<&Table> for Model {
                 .to_string();
             model.directives[number_of_directives].push_str("])");
         }
-        model.name_column_width = model.fields.iter().map(|f| f.name.len()).max().unwrap();
-        model.field_type_column_width = model
-            .fields
-            .iter()
-            .map(|f| f.field_type.len())
-            .max()
-            .unwrap();
-        // todo: add directives
+        model.set_column_widths();
+        model.add_key_directives(table);
         model
     }
 }
 
+/// A field type without its optional or list marker.
+pub fn base_type(field_type: &str) -> &str {
+    field_type.trim_end_matches('?').trim_end_matches("[]")
+}
+
+/// Renames the columns in a directive's field list, such as the `a, b` in `@@id([a, b])`.
+fn rename_directive_columns(directive: &str, renames: &HashMap<String, String>) -> String {
+    let (Some(start), Some(end)) = (directive.find('['), directive.find(']')) else {
+        return directive.to_string();
+    };
+    let columns: Vec<String> = directive[start + 1..end]
+        .split(',')
+        .map(|column| {
+            let column = column.trim();
+            renames
+                .get(column)
+                .cloned()
+                .unwrap_or_else(|| column.to_string())
+        })
+        .collect();
+    format!(
+        "{}{}{}",
+        &directive[..start + 1],
+        columns.join(", "),
+        &directive[end..]
+    )
+}
+
+/// An @@index directive covering each of the index's columns in order.
+fn index_directive(index_keys: &[IndexKey]) -> String {
+    let column_names: Vec<&str> = index_keys
+        .iter()
+        .map(|index_key| index_key.column_name.as_str())
+        .collect();
+    format!(
+        "@@index([{}], map: \"{}\")",
+        column_names.join(", "),
+        index_keys[0].constraint_name
+    )
+}
+
 impl PartialEq for Model {
     fn eq(&self, other: &Self) -> bool {
-        if !self.name.eq(&other.name) {
-            println!(
-                "found mismatching name on {}. self: {}, other: {}",
-                self.name, self.name, other.name
-            );
-            return false;
-        }
-        for directive in self.directives.iter() {
-            let other_directive = other.directives.iter().find(|d| d == &directive);
-            if other_directive.is_none() {
-                println!("directive {} not found in other {}", directive, self.name);
-                return false;
-            }
-        }
-        for field in self.fields.iter() {
-            println!("comparing field {} to other", field.name);
-            let other_field = other.fields.iter().find(|f| f.name == field.name);
-            if other_field.is_none() {
-                println!("field {} not found in other", field.name);
-                return false;
-            }
-            if field.to_owned() != other_field.expect("other field to exist").to_owned() {
-                println!("field {} not equal to other", field.name);
-                return false;
-            }
-        }
-        if self.name_column_width != other.name_column_width {
-            println!(
-                "found mismatching name_column_width on {}. self: {}, other: {}",
-                self.name, self.name_column_width, other.name_column_width
-            );
-            return false;
-        }
-        if self.field_type_column_width != other.field_type_column_width {
-            println!(
-                "found mismatching field_type_column_width on {}. self: {}, other: {}",
-                self.name, self.field_type_column_width, other.field_type_column_width
-            );
-            return false;
-        }
-        true
+        self.name == other.name
+            && self.documentation == other.documentation
+            && self
+                .directives
+                .iter()
+                .all(|directive| other.directives.contains(directive))
+            && self.fields.iter().all(|field| {
+                other
+                    .fields
+                    .iter()
+                    .any(|other_field| other_field.name == field.name && other_field == field)
+            })
+            && self.name_column_width == other.name_column_width
+            && self.field_type_column_width == other.field_type_column_width
     }
 }
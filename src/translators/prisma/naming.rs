@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+/// How model and field names are derived from table and column names.
+#[derive(Serialize, PartialEq, Eq, Deserialize, Copy, Clone, Debug, Default)]
+pub enum NamingPolicy {
+    /// Keep the database's names as they are.
+    #[default]
+    Database,
+    /// PascalCase singular model names and camelCase field names, mapped back to the database
+    /// names with @@map and @map.
+    Conventional,
+}
+
+impl NamingPolicy {
+    pub fn from_string(naming: &str) -> Option<NamingPolicy> {
+        match naming {
+            "database" => Some(NamingPolicy::Database),
+            "conventional" => Some(NamingPolicy::Conventional),
+            _ => None,
+        }
+    }
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Self::Database => "database",
+            Self::Conventional => "conventional",
+        }
+    }
+    pub fn all_as_array() -> Vec<NamingPolicy> {
+        vec![NamingPolicy::Database, NamingPolicy::Conventional]
+    }
+    pub fn all_as_str_array() -> Vec<&'static str> {
+        NamingPolicy::all_as_array()
+            .into_iter()
+            .map(|naming| naming.as_string())
+            .collect()
+    }
+
+    /// The model name for a table, `user_accounts` becoming `UserAccount`.
+    pub fn model_name(&self, table_name: &str) -> String {
+        match self {
+            Self::Database => table_name.to_string(),
+            Self::Conventional => {
+                let mut words = words(table_name);
+                if let Some(last) = words.last_mut() {
+                    *last = singular(last);
+                }
+                words.iter().map(|word| capitalized(word)).collect()
+            }
+        }
+    }
+
+    /// The field name for a column, `created_at` becoming `createdAt`.
+    pub fn field_name(&self, column_name: &str) -> String {
+        match self {
+            Self::Database => column_name.to_string(),
+            Self::Conventional => {
+                let words = words(column_name);
+                let mut name = String::new();
+                for (i, word) in words.iter().enumerate() {
+                    match i {
+                        0 => name.push_str(&word.to_lowercase()),
+                        _ => name.push_str(&capitalized(word)),
+                    }
+                }
+                name
+            }
+        }
+    }
+}
+
+/// Splits a name on underscores, dashes, spaces and lowercase to uppercase boundaries.
+fn words(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut previous_is_lowercase = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_is_lowercase = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_is_lowercase && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_is_lowercase = c.is_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalized(word: &str) -> String {
+    let lowercase = word.to_lowercase();
+    let mut chars = lowercase.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A best effort singular of an English plural, good enough for table names.
+fn singular(word: &str) -> String {
+    let lowercase = word.to_lowercase();
+    if let Some(stem) = lowercase.strip_suffix("ies") {
+        if !stem.is_empty() {
+            return format!("{}y", &word[..stem.len()]);
+        }
+    }
+    for suffix in ["sses", "shes", "ches", "xes", "zes"] {
+        if lowercase.ends_with(suffix) {
+            return word[..word.len() - 2].to_string();
+        }
+    }
+    if lowercase.ends_with('s')
+        && !lowercase.ends_with("ss")
+        && !lowercase.ends_with("us")
+        && !lowercase.ends_with("is")
+        && lowercase.len() > 1
+    {
+        return word[..word.len() - 1].to_string();
+    }
+    word.to_string()
+}
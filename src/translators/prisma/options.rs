@@ -0,0 +1,110 @@
+use crate::translators::prisma::{generator::Generator, model::Model};
+use serde::{Deserialize, Serialize};
+
+/// How the datasource block is written, apart from its provider which follows the database.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DatasourceOptions {
+    pub name: String,
+    /// The environment variable holding the connection url.
+    pub url_env: String,
+    /// `prisma` for databases without foreign keys, where Prisma emulates them.
+    #[serde(default)]
+    pub relation_mode: Option<String>,
+    /// The environment variable holding the shadow database's connection url.
+    #[serde(default)]
+    pub shadow_database_url_env: Option<String>,
+}
+
+impl Default for DatasourceOptions {
+    fn default() -> Self {
+        DatasourceOptions {
+            name: String::from("db"),
+            url_env: String::from("DATABASE_URL"),
+            relation_mode: None,
+            shadow_database_url_env: None,
+        }
+    }
+}
+
+/// Models written to a file of their own in a schema folder.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ModelGroup {
+    /// The file's name, without the `.prisma` extension.
+    pub name: String,
+    /// The tables whose models go in the file.
+    pub tables: Vec<String>,
+}
+
+impl ModelGroup {
+    /// Whether the model goes in the group's file, by its table's name or its own.
+    pub fn contains(&self, model: &Model) -> bool {
+        self.tables
+            .iter()
+            .any(|table| table == model.table_name() || table == model.get_name())
+    }
+}
+
+/// Whether a Prisma mapping is written as one file or as a schema folder.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub enum PrismaLayout {
+    /// One schema file at the mapping's path.
+    #[default]
+    File,
+    /// The mapping's path is a folder, with the generators and datasource in `schema.prisma`,
+    /// a file for each group of models, a file for each model outside the groups and the enums
+    /// in `enums.prisma`.
+    Folder {
+        #[serde(default)]
+        groups: Vec<ModelGroup>,
+    },
+}
+
+/// How the schema for a Prisma disk mapping is written.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PrismaOptions {
+    #[serde(default)]
+    pub datasource: DatasourceOptions,
+    #[serde(default = "default_generators")]
+    pub generators: Vec<Generator>,
+    #[serde(default)]
+    pub layout: PrismaLayout,
+}
+
+impl Default for PrismaOptions {
+    fn default() -> Self {
+        PrismaOptions {
+            datasource: DatasourceOptions::default(),
+            generators: default_generators(),
+            layout: PrismaLayout::default(),
+        }
+    }
+}
+
+fn default_generators() -> Vec<Generator> {
+    vec![Generator::new()]
+}
+
+/// Writes a generator or datasource block, lining up the `=` of its properties the way
+/// `prisma format` does.
+pub(crate) fn config_block(keyword: &str, name: &str, properties: &[(&str, String)]) -> String {
+    let width = properties
+        .iter()
+        .map(|(key, _)| key.len())
+        .max()
+        .unwrap_or(0);
+    let mut text = format!("{} {} {{\n", keyword, name);
+    for (key, value) in properties {
+        text.push_str(&format!("  {:width$} = {}\n", key, value));
+    }
+    text.push('}');
+    text
+}
+
+/// A list of strings as written in a schema, such as `["a", "b"]`.
+pub(crate) fn string_list(values: &[String]) -> String {
+    let values: Vec<String> = values
+        .iter()
+        .map(|value| format!("\"{}\"", value))
+        .collect();
+    format!("[{}]", values.join(", "))
+}
@@ -0,0 +1,906 @@
+use std::fmt;
+use std::ops::Range;
+
+/// A line and column in a schema file, both counted from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Why a schema file couldn't be read, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub position: Position,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Whitespace,
+    Newline,
+    /// A `//` comment, up to the end of its line.
+    Comment,
+    /// A `///` comment, documenting what follows it.
+    DocComment,
+    Identifier,
+    String,
+    Number,
+    LeftBrace,
+    RightBrace,
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Colon,
+    Equals,
+    Question,
+    Dot,
+    At,
+    DoubleAt,
+}
+
+/// A piece of a schema file. Every character of the file, whitespace included, is in a token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub position: Position,
+}
+
+/// Splits a schema file into tokens.
+pub fn tokenize(text: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens: Vec<Token> = vec![];
+    let mut i = 0;
+    let mut position = Position { line: 1, column: 1 };
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let kind = match c {
+            '\n' => {
+                i += 1;
+                TokenKind::Newline
+            }
+            ' ' | '\t' | '\r' => {
+                while i < chars.len() && matches!(chars[i], ' ' | '\t' | '\r') {
+                    i += 1;
+                }
+                TokenKind::Whitespace
+            }
+            '/' if next == Some('/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                match chars.get(start + 2) {
+                    Some('/') => TokenKind::DocComment,
+                    _ => TokenKind::Comment,
+                }
+            }
+            '"' => {
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None | Some('\n') => {
+                            return Err(ParseError {
+                                message: "unterminated string".to_string(),
+                                position,
+                            })
+                        }
+                        Some('\\') => i += 2,
+                        Some('"') => {
+                            i += 1;
+                            break;
+                        }
+                        Some(_) => i += 1,
+                    }
+                }
+                TokenKind::String
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                TokenKind::Identifier
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                TokenKind::Number
+            }
+            '@' if next == Some('@') => {
+                i += 2;
+                TokenKind::DoubleAt
+            }
+            _ => {
+                i += 1;
+                match c {
+                    '{' => TokenKind::LeftBrace,
+                    '}' => TokenKind::RightBrace,
+                    '(' => TokenKind::LeftParen,
+                    ')' => TokenKind::RightParen,
+                    '[' => TokenKind::LeftBracket,
+                    ']' => TokenKind::RightBracket,
+                    ',' => TokenKind::Comma,
+                    ':' => TokenKind::Colon,
+                    '=' => TokenKind::Equals,
+                    '?' => TokenKind::Question,
+                    '.' => TokenKind::Dot,
+                    '@' => TokenKind::At,
+                    _ => {
+                        return Err(ParseError {
+                            message: format!("unexpected character `{}`", c),
+                            position,
+                        })
+                    }
+                }
+            }
+        };
+        let i_end = i.min(chars.len());
+        tokens.push(Token {
+            kind,
+            text: chars[start..i_end].iter().collect(),
+            position,
+        });
+        position = match kind {
+            TokenKind::Newline => Position {
+                line: position.line + 1,
+                column: 1,
+            },
+            _ => Position {
+                line: position.line,
+                column: position.column + (i_end - start),
+            },
+        };
+        i = i_end;
+    }
+    Ok(tokens)
+}
+
+/// A value given to an attribute, a function or a generator or datasource property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+    /// A string, with its escapes resolved.
+    String(String),
+    Number(String),
+    /// A bare name, such as `Cascade` or an enum value.
+    Constant(String),
+    Function {
+        name: String,
+        arguments: Vec<Argument>,
+    },
+    Array(Vec<Expression>),
+}
+
+impl Expression {
+    /// The expression as it would be written in a schema.
+    pub fn as_text(&self) -> String {
+        match self {
+            Self::String(value) => format!("\"{}\"", escape(value)),
+            Self::Number(value) | Self::Constant(value) => value.clone(),
+            Self::Function { name, arguments } => {
+                format!("{}({})", name, arguments_text(arguments))
+            }
+            Self::Array(values) => format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|value| value.as_text())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The strings in an array of strings, such as `previewFeatures`.
+    pub fn as_strings(&self) -> Option<Vec<String>> {
+        match self {
+            Self::Array(values) => values
+                .iter()
+                .map(|value| value.as_str().map(str::to_string))
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /// The names in an array of constants, such as the `fields: [a, b]` of a relation.
+    pub fn as_names(&self) -> Option<Vec<String>> {
+        match self {
+            Self::Array(values) => values
+                .iter()
+                .map(|value| match value {
+                    Self::Constant(name) => Some(name.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn unescape(value: &str) -> String {
+    let mut resp = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            resp.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => resp.push('\n'),
+            Some('t') => resp.push('\t'),
+            Some(escaped) => resp.push(escaped),
+            None => resp.push('\\'),
+        }
+    }
+    resp
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Argument {
+    pub name: Option<String>,
+    pub value: Expression,
+}
+
+impl Argument {
+    pub fn as_text(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{}: {}", name, self.value.as_text()),
+            None => self.value.as_text(),
+        }
+    }
+}
+
+fn arguments_text(arguments: &[Argument]) -> String {
+    arguments
+        .iter()
+        .map(|argument| argument.as_text())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// A field attribute such as `@db.VarChar(191)`, or a block attribute such as `@@id([a, b])`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    /// The name after the `@`, such as `db.VarChar`.
+    pub name: String,
+    pub is_block: bool,
+    pub arguments: Vec<Argument>,
+    pub position: Position,
+    /// The attribute's tokens in the file. A block attribute's run from its documentation to
+    /// the end of its line, comment included.
+    tokens: Range<usize>,
+}
+
+impl Attribute {
+    pub fn as_text(&self) -> String {
+        let at = if self.is_block { "@@" } else { "@" };
+        match self.arguments.is_empty() {
+            true => format!("{}{}", at, self.name),
+            false => format!("{}{}({})", at, self.name, arguments_text(&self.arguments)),
+        }
+    }
+
+    /// The argument with the given name, or the first unnamed one when `name` is None.
+    pub fn argument(&self, name: Option<&str>) -> Option<&Expression> {
+        self.arguments
+            .iter()
+            .find(|argument| argument.name.as_deref() == name)
+            .map(|argument| &argument.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldType {
+    pub name: String,
+    /// The database type of an `Unsupported("...")` field.
+    pub unsupported: Option<String>,
+    pub is_optional: bool,
+    pub is_list: bool,
+}
+
+impl FieldType {
+    pub fn as_text(&self) -> String {
+        let mut text = match &self.unsupported {
+            Some(database_type) => format!("{}(\"{}\")", self.name, escape(database_type)),
+            None => self.name.clone(),
+        };
+        if self.is_list {
+            text.push_str("[]");
+        }
+        if self.is_optional {
+            text.push('?');
+        }
+        text
+    }
+}
+
+/// A field of a model, view or type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDeclaration {
+    pub name: String,
+    pub field_type: FieldType,
+    pub attributes: Vec<Attribute>,
+    pub documentation: Vec<String>,
+    /// A `//` comment after the field on the same line.
+    pub comment: Option<String>,
+    pub position: Position,
+    /// The field's tokens in the file, from its documentation to the end of its line, comment
+    /// included.
+    tokens: Range<usize>,
+}
+
+impl FieldDeclaration {
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+}
+
+/// A value of an enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumValueDeclaration {
+    pub name: String,
+    pub attributes: Vec<Attribute>,
+    pub documentation: Vec<String>,
+    pub comment: Option<String>,
+    pub position: Position,
+}
+
+/// A `key = value` line of a generator or datasource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    pub name: String,
+    pub value: Expression,
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Model,
+    Enum,
+    View,
+    Type,
+    Generator,
+    Datasource,
+}
+
+impl BlockKind {
+    fn from_keyword(keyword: &str) -> Option<BlockKind> {
+        match keyword {
+            "model" => Some(BlockKind::Model),
+            "enum" => Some(BlockKind::Enum),
+            "view" => Some(BlockKind::View),
+            "type" => Some(BlockKind::Type),
+            "generator" => Some(BlockKind::Generator),
+            "datasource" => Some(BlockKind::Datasource),
+            _ => None,
+        }
+    }
+}
+
+/// A top-level block of a schema file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub kind: BlockKind,
+    pub name: String,
+    pub documentation: Vec<String>,
+    pub fields: Vec<FieldDeclaration>,
+    pub values: Vec<EnumValueDeclaration>,
+    pub properties: Vec<Property>,
+    pub attributes: Vec<Attribute>,
+    pub position: Position,
+    /// The block's tokens in the file, from its documentation to its closing brace.
+    tokens: Range<usize>,
+}
+
+impl Block {
+    pub fn property(&self, name: &str) -> Option<&Expression> {
+        self.properties
+            .iter()
+            .find(|property| property.name == name)
+            .map(|property| &property.value)
+    }
+}
+
+/// A parsed schema file, which keeps every token so it can be printed back exactly as it was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaFile {
+    pub blocks: Vec<Block>,
+    tokens: Vec<Token>,
+}
+
+impl SchemaFile {
+    pub fn parse(text: &str) -> Result<SchemaFile, ParseError> {
+        let tokens = tokenize(text)?;
+        let blocks = Parser {
+            tokens: &tokens,
+            index: 0,
+        }
+        .parse_blocks()?;
+        Ok(SchemaFile { blocks, tokens })
+    }
+
+    /// The file's text, identical to the text it was parsed from.
+    pub fn as_text(&self) -> String {
+        self.tokens
+            .iter()
+            .map(|token| token.text.as_str())
+            .collect()
+    }
+
+    /// A block's text as it was written, documentation included.
+    pub fn block_text(&self, block: &Block) -> String {
+        self.text(block.tokens.clone())
+    }
+
+    /// A block attribute's line as it was written, documentation and comment included.
+    pub fn attribute_text(&self, attribute: &Attribute) -> String {
+        self.text(attribute.tokens.clone())
+    }
+
+    /// The text around the blocks: what's before each block, then what's after the last.
+    pub fn trivia(&self) -> Vec<String> {
+        let mut trivia: Vec<String> = vec![];
+        let mut end = 0;
+        for block in self.blocks.iter() {
+            trivia.push(self.text(end..block.tokens.start));
+            end = block.tokens.end;
+        }
+        trivia.push(self.text(end..self.tokens.len()));
+        trivia
+    }
+
+    /// What a block is made of on disk, in the order it was written: its header up to the
+    /// opening brace, each field and block attribute, and the text between them.
+    pub fn block_parts<'a>(&'a self, block: &'a Block) -> Vec<BlockPart<'a>> {
+        let header_end = block.tokens.start
+            + self.tokens[block.tokens.clone()]
+                .iter()
+                .position(|token| token.kind == TokenKind::LeftBrace)
+                .map(|index| index + 1)
+                .unwrap_or(0);
+        let mut items: Vec<(Range<usize>, BlockPart)> = block
+            .fields
+            .iter()
+            .map(|field| (field.tokens.clone(), BlockPart::Field(field)))
+            .chain(
+                block
+                    .attributes
+                    .iter()
+                    .map(|attribute| (attribute.tokens.clone(), BlockPart::Attribute(attribute))),
+            )
+            .collect();
+        items.sort_by_key(|(tokens, _)| tokens.start);
+        let mut parts = vec![BlockPart::Header(self.text(block.tokens.start..header_end))];
+        let mut end = header_end;
+        for (tokens, item) in items {
+            parts.push(BlockPart::Trivia(self.text(end..tokens.start)));
+            parts.push(item);
+            end = tokens.end;
+        }
+        // The closing brace is the block's last token.
+        parts.push(BlockPart::Trivia(self.text(end..block.tokens.end - 1)));
+        parts
+    }
+
+    fn text(&self, tokens: Range<usize>) -> String {
+        self.tokens[tokens]
+            .iter()
+            .map(|token| token.text.as_str())
+            .collect()
+    }
+}
+
+/// A piece of a block, as given by [`SchemaFile::block_parts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockPart<'a> {
+    /// The block's documentation, keyword, name and opening brace.
+    Header(String),
+    /// Whitespace and comments that belong to neither the field before nor the one after.
+    Trivia(String),
+    Field(&'a FieldDeclaration),
+    Attribute(&'a Attribute),
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    index: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn peek_kind(&self) -> Option<TokenKind> {
+        self.peek().map(|token| token.kind)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.index);
+        self.index += 1;
+        token
+    }
+
+    /// Where the next token starts, or the end of the file.
+    fn position(&self) -> Position {
+        match self.tokens.get(self.index) {
+            Some(token) => token.position,
+            None => match self.tokens.last() {
+                Some(token) => Position {
+                    line: token.position.line,
+                    column: token.position.column + token.text.chars().count(),
+                },
+                None => Position { line: 1, column: 1 },
+            },
+        }
+    }
+
+    fn error<T>(&self, expected: &str) -> Result<T, ParseError> {
+        let found = match self.peek() {
+            Some(token) if token.kind == TokenKind::Newline => "the end of the line".to_string(),
+            Some(token) => format!("`{}`", token.text),
+            None => "the end of the file".to_string(),
+        };
+        Err(ParseError {
+            message: format!("expected {}, found {}", expected, found),
+            position: self.position(),
+        })
+    }
+
+    fn expect(&mut self, kind: TokenKind, expected: &str) -> Result<String, ParseError> {
+        match self.peek_kind() {
+            Some(next) if next == kind => Ok(self.next().unwrap().text.clone()),
+            _ => self.error(expected),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek_kind() == Some(TokenKind::Whitespace) {
+            self.index += 1;
+        }
+    }
+
+    /// Skips whitespace, newlines and comments, as allowed inside brackets and parentheses.
+    fn skip_trivia(&mut self) {
+        while matches!(
+            self.peek_kind(),
+            Some(
+                TokenKind::Whitespace
+                    | TokenKind::Newline
+                    | TokenKind::Comment
+                    | TokenKind::DocComment
+            )
+        ) {
+            self.index += 1;
+        }
+    }
+
+    /// Skips blank lines and comments, returning the doc comments directly above what follows
+    /// and the index of the first of them.
+    fn documentation(&mut self) -> (Vec<String>, usize) {
+        let mut documentation: Vec<String> = vec![];
+        let mut start = self.index;
+        // A blank line separates doc comments from what follows them.
+        let mut blank_line = true;
+        loop {
+            match self.peek_kind() {
+                Some(TokenKind::Whitespace) => self.index += 1,
+                Some(TokenKind::Newline) => {
+                    self.index += 1;
+                    if blank_line {
+                        documentation.clear();
+                    }
+                    blank_line = true;
+                }
+                Some(TokenKind::Comment) => {
+                    self.index += 1;
+                    blank_line = false;
+                }
+                Some(TokenKind::DocComment) => {
+                    blank_line = false;
+                    if documentation.is_empty() {
+                        start = self.index;
+                    }
+                    let text = &self.next().unwrap().text;
+                    documentation.push(text[3..].trim().to_string());
+                }
+                _ => break,
+            }
+        }
+        if documentation.is_empty() {
+            start = self.index;
+        }
+        // Include the indentation in front of the first doc comment.
+        while start > 0 && self.tokens[start - 1].kind == TokenKind::Whitespace {
+            start -= 1;
+        }
+        (documentation, start)
+    }
+
+    fn parse_blocks(&mut self) -> Result<Vec<Block>, ParseError> {
+        let mut blocks: Vec<Block> = vec![];
+        loop {
+            let (documentation, start) = self.documentation();
+            if self.peek().is_none() {
+                return Ok(blocks);
+            }
+            let position = self.position();
+            let keyword = self.expect(TokenKind::Identifier, "a block")?;
+            let Some(kind) = BlockKind::from_keyword(&keyword) else {
+                return Err(ParseError {
+                    message: format!("unknown block type `{}`", keyword),
+                    position,
+                });
+            };
+            self.skip_whitespace();
+            let name = self.expect(TokenKind::Identifier, "a block name")?;
+            self.skip_whitespace();
+            self.expect(TokenKind::LeftBrace, "`{`")?;
+            let mut block = Block {
+                kind,
+                name,
+                documentation,
+                fields: vec![],
+                values: vec![],
+                properties: vec![],
+                attributes: vec![],
+                position,
+                tokens: start..start,
+            };
+            self.parse_block_body(&mut block)?;
+            block.tokens = start..self.index;
+            blocks.push(block);
+        }
+    }
+
+    fn parse_block_body(&mut self, block: &mut Block) -> Result<(), ParseError> {
+        loop {
+            let (documentation, start) = self.documentation();
+            let position = self.position();
+            match self.peek_kind() {
+                Some(TokenKind::RightBrace) => {
+                    self.index += 1;
+                    return Ok(());
+                }
+                Some(TokenKind::DoubleAt) => {
+                    let mut attribute = self.parse_attribute()?;
+                    self.end_of_line()?;
+                    attribute.tokens = start..self.line_end();
+                    block.attributes.push(attribute);
+                }
+                Some(TokenKind::Identifier) => {
+                    let name = self.next().unwrap().text.clone();
+                    self.skip_whitespace();
+                    match block.kind {
+                        BlockKind::Generator | BlockKind::Datasource => {
+                            self.expect(TokenKind::Equals, "`=`")?;
+                            self.skip_whitespace();
+                            let value = self.parse_expression()?;
+                            self.end_of_line()?;
+                            block.properties.push(Property {
+                                name,
+                                value,
+                                position,
+                            });
+                        }
+                        BlockKind::Enum => {
+                            let attributes = self.parse_field_attributes()?;
+                            let comment = self.end_of_line()?;
+                            block.values.push(EnumValueDeclaration {
+                                name,
+                                attributes,
+                                documentation,
+                                comment,
+                                position,
+                            });
+                        }
+                        BlockKind::Model | BlockKind::View | BlockKind::Type => {
+                            let field_type = self.parse_field_type()?;
+                            let attributes = self.parse_field_attributes()?;
+                            let comment = self.end_of_line()?;
+                            block.fields.push(FieldDeclaration {
+                                name,
+                                field_type,
+                                attributes,
+                                documentation,
+                                comment,
+                                position,
+                                tokens: start..self.line_end(),
+                            });
+                        }
+                    }
+                }
+                _ => return self.error("a field, an attribute or `}`"),
+            }
+        }
+    }
+
+    /// Where the line just ended by [`Parser::end_of_line`] stops, before its newline.
+    fn line_end(&self) -> usize {
+        match self.tokens.get(self.index - 1) {
+            Some(token) if token.kind == TokenKind::Newline => self.index - 1,
+            _ => self.index,
+        }
+    }
+
+    /// Expects the end of a line inside a block, returning the comment at the end of it.
+    fn end_of_line(&mut self) -> Result<Option<String>, ParseError> {
+        self.skip_whitespace();
+        let mut comment = None;
+        if self.peek_kind() == Some(TokenKind::Comment) {
+            comment = Some(self.next().unwrap().text.clone());
+        }
+        match self.peek_kind() {
+            Some(TokenKind::Newline) => {
+                self.index += 1;
+                Ok(comment)
+            }
+            Some(TokenKind::RightBrace) => Ok(comment),
+            _ => self.error("the end of the line"),
+        }
+    }
+
+    fn parse_field_type(&mut self) -> Result<FieldType, ParseError> {
+        let name = self.expect(TokenKind::Identifier, "a field type")?;
+        let mut unsupported = None;
+        if name == "Unsupported" && self.peek_kind() == Some(TokenKind::LeftParen) {
+            self.index += 1;
+            self.skip_trivia();
+            let database_type = self.expect(TokenKind::String, "a string")?;
+            unsupported = Some(unescape(&database_type[1..database_type.len() - 1]));
+            self.skip_trivia();
+            self.expect(TokenKind::RightParen, "`)`")?;
+        }
+        let mut is_list = false;
+        if self.peek_kind() == Some(TokenKind::LeftBracket) {
+            self.index += 1;
+            self.expect(TokenKind::RightBracket, "`]`")?;
+            is_list = true;
+        }
+        let mut is_optional = false;
+        if self.peek_kind() == Some(TokenKind::Question) {
+            self.index += 1;
+            is_optional = true;
+        }
+        Ok(FieldType {
+            name,
+            unsupported,
+            is_optional,
+            is_list,
+        })
+    }
+
+    fn parse_field_attributes(&mut self) -> Result<Vec<Attribute>, ParseError> {
+        let mut attributes: Vec<Attribute> = vec![];
+        self.skip_whitespace();
+        while self.peek_kind() == Some(TokenKind::At) {
+            attributes.push(self.parse_attribute()?);
+            self.skip_whitespace();
+        }
+        Ok(attributes)
+    }
+
+    fn parse_attribute(&mut self) -> Result<Attribute, ParseError> {
+        let start = self.index;
+        let position = self.position();
+        let is_block = self.next().unwrap().kind == TokenKind::DoubleAt;
+        let mut name = self.expect(TokenKind::Identifier, "an attribute name")?;
+        while self.peek_kind() == Some(TokenKind::Dot) {
+            self.index += 1;
+            name.push('.');
+            name.push_str(&self.expect(TokenKind::Identifier, "an attribute name")?);
+        }
+        let mut arguments = vec![];
+        if self.peek_kind() == Some(TokenKind::LeftParen) {
+            arguments = self.parse_arguments()?;
+        }
+        Ok(Attribute {
+            name,
+            is_block,
+            arguments,
+            position,
+            tokens: start..self.index,
+        })
+    }
+
+    /// Parses a parenthesised argument list, which may span several lines.
+    fn parse_arguments(&mut self) -> Result<Vec<Argument>, ParseError> {
+        self.expect(TokenKind::LeftParen, "`(`")?;
+        let mut arguments: Vec<Argument> = vec![];
+        loop {
+            self.skip_trivia();
+            if self.peek_kind() == Some(TokenKind::RightParen) {
+                self.index += 1;
+                return Ok(arguments);
+            }
+            let mut name = None;
+            let named = self.peek_kind() == Some(TokenKind::Identifier)
+                && self
+                    .tokens
+                    .get(self.index + 1)
+                    .is_some_and(|token| token.kind == TokenKind::Colon);
+            if named {
+                name = Some(self.next().unwrap().text.clone());
+                self.index += 1;
+                self.skip_trivia();
+            }
+            let value = self.parse_expression()?;
+            arguments.push(Argument { name, value });
+            self.skip_trivia();
+            match self.peek_kind() {
+                Some(TokenKind::Comma) => self.index += 1,
+                Some(TokenKind::RightParen) => {}
+                _ => return self.error("`,` or `)`"),
+            }
+        }
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, ParseError> {
+        match self.peek_kind() {
+            Some(TokenKind::String) => {
+                let text = &self.next().unwrap().text;
+                Ok(Expression::String(unescape(&text[1..text.len() - 1])))
+            }
+            Some(TokenKind::Number) => Ok(Expression::Number(self.next().unwrap().text.clone())),
+            Some(TokenKind::Identifier) => {
+                let name = self.next().unwrap().text.clone();
+                if self.peek_kind() == Some(TokenKind::LeftParen) {
+                    let arguments = self.parse_arguments()?;
+                    return Ok(Expression::Function { name, arguments });
+                }
+                Ok(Expression::Constant(name))
+            }
+            Some(TokenKind::LeftBracket) => {
+                self.index += 1;
+                let mut values: Vec<Expression> = vec![];
+                loop {
+                    self.skip_trivia();
+                    if self.peek_kind() == Some(TokenKind::RightBracket) {
+                        self.index += 1;
+                        return Ok(Expression::Array(values));
+                    }
+                    values.push(self.parse_expression()?);
+                    self.skip_trivia();
+                    match self.peek_kind() {
+                        Some(TokenKind::Comma) => self.index += 1,
+                        Some(TokenKind::RightBracket) => {}
+                        _ => return self.error("`,` or `]`"),
+                    }
+                }
+            }
+            _ => self.error("a value"),
+        }
+    }
+}
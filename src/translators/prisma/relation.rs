@@ -1,7 +1,11 @@
+use crate::remotes::sql::ReferentialAction;
+use crate::translators::prisma::parser::Attribute;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Deserialize, Serialize, Debug, Clone, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct Relation {
+    name: Option<String>,
     map: Option<String>,
     fields: Option<Vec<String>>,
     references: Option<Vec<String>>,
@@ -10,167 +14,161 @@ pub struct Relation {
 }
 
 impl Relation {
+    /// Create a relation, leaving the referential actions and map unset.
+    pub fn new(
+        name: Option<String>,
+        fields: Option<Vec<String>>,
+        references: Option<Vec<String>>,
+    ) -> Relation {
+        Relation {
+            name,
+            map: None,
+            fields,
+            references,
+            on_update: None,
+            on_delete: None,
+        }
+    }
+
+    /// Set the onDelete and onUpdate arguments from the database's referential actions.
+    pub fn set_referential_actions(
+        &mut self,
+        on_delete: Option<ReferentialAction>,
+        on_update: Option<ReferentialAction>,
+    ) {
+        self.on_delete = on_delete.map(|action| prisma_referential_action(action).to_string());
+        self.on_update = on_update.map(|action| prisma_referential_action(action).to_string());
+    }
+
+    /// Rename the columns on either side of the relation, leaving any not in the maps as they are.
+    pub fn rename_columns(
+        &mut self,
+        fields: &HashMap<String, String>,
+        references: &HashMap<String, String>,
+    ) {
+        let rename = |names: &mut Vec<String>, renames: &HashMap<String, String>| {
+            for name in names.iter_mut() {
+                if let Some(renamed) = renames.get(name) {
+                    *name = renamed.clone();
+                }
+            }
+        };
+        if let Some(names) = self.fields.as_mut() {
+            rename(names, fields);
+        }
+        if let Some(names) = self.references.as_mut() {
+            rename(names, references);
+        }
+    }
+
+    pub fn get_map(&self) -> Option<&str> {
+        self.map.as_deref()
+    }
+
+    pub fn get_fields(&self) -> Option<&[String]> {
+        self.fields.as_deref()
+    }
+
+    pub fn get_references(&self) -> Option<&[String]> {
+        self.references.as_deref()
+    }
+
+    /// The database's referential actions, read back from the onDelete and onUpdate arguments.
+    pub fn referential_actions(&self) -> (Option<ReferentialAction>, Option<ReferentialAction>) {
+        (
+            self.on_delete.as_deref().and_then(referential_action),
+            self.on_update.as_deref().and_then(referential_action),
+        )
+    }
+
     pub fn as_text(&self) -> String {
         let mut resp = String::new();
         resp.push_str("@relation(");
-        if self.fields.is_some() {
+        if let Some(name) = &self.name {
+            resp.push('"');
+            resp.push_str(name);
+            resp.push('"');
+        }
+        if let Some(fields) = &self.fields {
+            if self.name.is_some() {
+                resp.push_str(", ");
+            }
             resp.push_str("fields: [");
-            resp.push_str(self.fields.clone().unwrap().join(", ").as_str());
-            resp.push_str("]");
+            resp.push_str(&fields.join(", "));
+            resp.push(']');
         }
-        if self.references.is_some() {
+        if let Some(references) = &self.references {
             if self.fields.is_some() {
                 resp.push_str(", ");
             }
             resp.push_str("references: [");
-            resp.push_str(self.references.clone().unwrap().join(", ").as_str());
-            resp.push_str("]");
+            resp.push_str(&references.join(", "));
+            resp.push(']');
         }
-        if self.map.is_some() {
+        if let Some(map) = &self.map {
             if self.references.is_some() {
                 resp.push_str(", ");
             }
             resp.push_str("map: \"");
-            resp.push_str(self.map.clone().unwrap().as_str());
-            resp.push_str("\"");
+            resp.push_str(map);
+            resp.push('"');
         }
-        if self.on_delete.is_some() {
+        if let Some(on_delete) = &self.on_delete {
             resp.push_str(", ");
             resp.push_str("onDelete: ");
-            resp.push_str(self.on_delete.clone().unwrap().as_str());
+            resp.push_str(on_delete);
         }
-        if self.on_update.is_some() {
+        if let Some(on_update) = &self.on_update {
             resp.push_str(", ");
             resp.push_str("onUpdate: ");
-            resp.push_str(self.on_update.clone().unwrap().as_str());
+            resp.push_str(on_update);
         }
-        resp.push_str(")");
+        resp.push(')');
         resp
     }
 
-    pub fn from_string(string: String) -> Relation {
-        let mut this_fields: Vec<String> = vec![];
-        let mut this_references: Vec<String> = vec![];
-        let mut this_map: String = String::new();
-        let mut this_on_update: String = String::new();
-        let mut this_on_delete: String = String::new();
-
-        println!("---> constructing relation from {}", string);
-
-        let mut new_string = string.clone();
-
-        new_string = new_string
-            .strip_prefix("@relation(")
-            .expect("input to be a relation string")
-            .to_string();
-
-        new_string = new_string
-            .strip_suffix(")")
-            .expect("relation string to have a closing paren")
-            .to_string();
-
-        let pieces = new_string.split(", ");
-
-        println!("---> pieces");
-        for piece in pieces {
-            if piece.starts_with("fields:") {
-                println!("{:?}", piece);
-                let new_piece = piece
-                    .strip_prefix("fields:[")
-                    .expect("fields to be formatted correctly")
-                    .strip_suffix("]")
-                    .expect("fields to be formatted correctly");
-                let new_pieces = Some(new_piece.split(", "));
-                for sub_piece in new_pieces.unwrap() {
-                    this_fields.push(String::from(sub_piece.to_string()));
-                }
-            } else if piece.starts_with("references:") {
-                let new_piece = piece
-                    .strip_prefix("references:[")
-                    .expect("references to be formatted correctly")
-                    .strip_suffix("]")
-                    .expect("references to be formatted correctly");
-                let new_pieces = Some(new_piece.split(", "));
-                for sub_piece in new_pieces.unwrap() {
-                    this_references.push(String::from(sub_piece.to_string()));
-                }
-            } else if piece.starts_with("onDelete:") {
-                this_on_delete = piece
-                    .strip_prefix("onDelete:")
-                    .expect("on delete to be formatted correctly")
-                    .to_string();
-            } else if piece.starts_with("onUpdate:") {
-                this_on_update = piece
-                    .strip_prefix("onUpdate:")
-                    .expect("on delete to be formatted correctly")
-                    .to_string();
-            } else if piece.starts_with("map:") {
-                this_map = piece
-                    .strip_prefix("map:\"")
-                    .expect("map to be formatted correctly")
-                    .strip_suffix("\"")
-                    .expect("closing double quotes to exist")
-                    .to_string();
-            } else {
-                panic!("unhandled relation {:?}", piece);
-            }
-        }
-
-        let fields = if this_fields.len() > 0 {
-            Some(this_fields)
-        } else {
-            None
-        };
-
-        let references = if this_references.len() > 0 {
-            Some(this_references)
-        } else {
-            None
-        };
-
-        let map = if this_map.len() > 0 {
-            Some(this_map)
-        } else {
-            None
-        };
-
-        let on_update = if this_on_update.len() > 0 {
-            Some(this_on_update)
-        } else {
-            None
+    /// Build a relation from its `@relation` attribute in a schema file.
+    pub fn from_attribute(attribute: &Attribute) -> Relation {
+        let string = |name: Option<&str>| {
+            attribute
+                .argument(name)
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
         };
-
-        let on_delete = if this_on_delete.len() > 0 {
-            Some(this_on_delete)
-        } else {
-            None
-        };
-
+        let constant = |name: &str| attribute.argument(Some(name)).map(|value| value.as_text());
         Relation {
-            fields,
-            references,
-            map,
-            on_update,
-            on_delete,
+            name: string(None).or_else(|| string(Some("name"))),
+            map: string(Some("map")),
+            fields: attribute
+                .argument(Some("fields"))
+                .and_then(|fields| fields.as_names()),
+            references: attribute
+                .argument(Some("references"))
+                .and_then(|references| references.as_names()),
+            on_update: constant("onUpdate"),
+            on_delete: constant("onDelete"),
         }
     }
 }
 
-impl PartialEq for Relation {
-    fn eq(&self, other: &Self) -> bool {
-        let resp = self.fields == other.fields
-            && self.references == other.references
-            && self.map == other.map
-            && self.on_update == other.on_update
-            && self.on_delete == other.on_delete;
-        if !resp {
-            println!("in relation");
-            println!("---> {:?} != {:?}", self.fields, other.fields);
-            println!("---> {:?} != {:?}", self.references, other.references);
-            println!("---> {:?} != {:?}", self.map, other.map);
-            println!("---> {:?} != {:?}", self.on_update, other.on_update);
-            println!("---> {:?} != {:?}", self.on_delete, other.on_delete);
-        }
-        resp
+fn prisma_referential_action(action: ReferentialAction) -> &'static str {
+    match action {
+        ReferentialAction::Cascade => "Cascade",
+        ReferentialAction::SetNull => "SetNull",
+        ReferentialAction::SetDefault => "SetDefault",
+        ReferentialAction::Restrict => "Restrict",
+        ReferentialAction::NoAction => "NoAction",
+    }
+}
+
+/// The database's referential action for a Prisma one, such as `SET NULL` for `SetNull`.
+fn referential_action(action: &str) -> Option<ReferentialAction> {
+    match action {
+        "Cascade" => Some(ReferentialAction::Cascade),
+        "SetNull" => Some(ReferentialAction::SetNull),
+        "SetDefault" => Some(ReferentialAction::SetDefault),
+        "Restrict" => Some(ReferentialAction::Restrict),
+        "NoAction" => Some(ReferentialAction::NoAction),
+        _ => None,
     }
 }
@@ -1,59 +1,326 @@
-use crate::remotes::sql::Table;
-use crate::translators::prisma::{data_source::Datasource, generator::Generator, model::Model};
+use crate::remotes::sql::{ForeignKey, Key, Table};
+use crate::translators::prisma::{
+    data_source::Datasource,
+    enumeration::Enum,
+    field::Field,
+    generator::Generator,
+    model::Model,
+    naming::NamingPolicy,
+    options::{ModelGroup, PrismaOptions},
+    parser::{BlockKind, SchemaFile},
+    relation::Relation,
+    tables,
+};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
 use std::io::BufRead;
 use std::io::BufReader;
 
+/// The file holding the generators and datasource in a schema folder.
+pub const SCHEMA_FILE: &str = "schema.prisma";
+/// The file holding the enums in a schema folder.
+pub const ENUMS_FILE: &str = "enums.prisma";
+
+/// The file of a schema folder a model is written to, its group's or one of its own.
+pub fn model_file_name(model: &Model, groups: &[ModelGroup]) -> String {
+    match groups.iter().find(|group| group.contains(model)) {
+        Some(group) => format!("{}.prisma", group.name),
+        None => format!("{}.prisma", model.get_name()),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq)]
 pub struct PrismaSchema {
-    generator: Generator,
+    generators: Vec<Generator>,
     datasource: Datasource,
     models: Vec<Model>,
+    enums: Vec<Enum>,
+}
+
+impl Default for PrismaSchema {
+    fn default() -> PrismaSchema {
+        PrismaSchema::new()
+    }
 }
 
 impl PrismaSchema {
     pub fn new() -> PrismaSchema {
         PrismaSchema {
-            generator: Generator::new(),
+            generators: vec![Generator::new()],
             datasource: Datasource::new(),
             models: vec![],
+            enums: vec![],
         }
     }
 
-    pub fn build(generator: Generator, datasource: Datasource, models: Vec<Model>) -> PrismaSchema {
+    pub fn build(
+        generators: Vec<Generator>,
+        datasource: Datasource,
+        models: Vec<Model>,
+        enums: Vec<Enum>,
+    ) -> PrismaSchema {
         PrismaSchema {
-            generator,
+            generators,
             datasource,
             models,
+            enums,
         }
     }
 
+    /// Write the datasource and generators a mapping is configured with.
+    pub fn apply_options(&mut self, options: &PrismaOptions) {
+        self.datasource.configure(&options.datasource);
+        self.generators = options.generators.clone();
+    }
+
     pub fn add_model(&mut self, model: Model) {
         self.models.push(model);
     }
 
+    pub fn add_enum(&mut self, enumeration: Enum) {
+        self.enums.push(enumeration);
+    }
+
+    pub fn get_models(&self) -> &[Model] {
+        &self.models
+    }
+
+    pub fn get_enums(&self) -> &[Enum] {
+        &self.enums
+    }
+
+    pub fn get_datasource(&self) -> &Datasource {
+        &self.datasource
+    }
+
+    /// A warning for each column written as `Unsupported`, which Prisma Client can't read or
+    /// write.
+    pub fn warnings(&self) -> Vec<String> {
+        self.models
+            .iter()
+            .flat_map(|model| {
+                model
+                    .get_fields()
+                    .iter()
+                    .filter(|field| field.field_type.starts_with("Unsupported("))
+                    .map(move |field| {
+                        format!(
+                            "{}.{} has a type Prisma doesn't support, writing it as {}",
+                            model.table_name(),
+                            field.map.as_deref().unwrap_or(&field.name),
+                            field.field_type.trim_end_matches('?')
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    fn find_model(&self, name: &str) -> Option<&Model> {
+        self.models.iter().find(|model| model.get_name() == name)
+    }
+
+    fn find_model_mut(&mut self, name: &str) -> Option<&mut Model> {
+        self.models
+            .iter_mut()
+            .find(|model| model.get_name() == name)
+    }
+
+    /// Add a relation field to the owning model and a back-relation field to the referenced
+    /// model for every foreign key in the tables.
+    fn add_relations(&mut self, tables: &[Table]) {
+        let foreign_keys: Vec<(&Table, &ForeignKey)> = tables
+            .iter()
+            .flat_map(|table| {
+                table.keys.keys.iter().filter_map(move |key| match key {
+                    Key::Foreign(foreign_key) => Some((table, foreign_key)),
+                    _ => None,
+                })
+            })
+            .collect();
+        for (table, foreign_key) in foreign_keys.iter() {
+            let referenced_name = foreign_key.referenced_table_name.as_str();
+            if self.find_model(referenced_name).is_none() {
+                continue;
+            }
+            // Prisma can only tell relations apart by name when more than one links the same
+            // pair of models.
+            let links = foreign_keys
+                .iter()
+                .filter(|(other_table, other_key)| {
+                    (other_table.name == table.name
+                        && other_key.referenced_table_name == referenced_name)
+                        || (other_table.name == referenced_name
+                            && other_key.referenced_table_name == table.name)
+                })
+                .count();
+            let mut names = RelationNames::new(table, foreign_key, links > 1);
+            let owning_model = self.find_model(&table.name).expect("owning model to exist");
+            let referenced_model = self
+                .find_model(referenced_name)
+                .expect("referenced model to exist");
+            if owning_model.has_field(&names.field) || referenced_model.has_field(&names.back_field)
+            {
+                names = RelationNames::new(table, foreign_key, true);
+            }
+            let is_required = foreign_key.column_names.iter().all(|column_name| {
+                table
+                    .description
+                    .iter()
+                    .find(|description| &description.field == column_name)
+                    .map(|description| description.null == "NO")
+                    .unwrap_or(false)
+            });
+            let is_one_to_one = is_unique_columns(table, &foreign_key.column_names);
+            let mut relation = Relation::new(
+                names.relation.clone(),
+                Some(foreign_key.column_names.clone()),
+                Some(foreign_key.referenced_column_names.clone()),
+            );
+            relation.set_referential_actions(foreign_key.on_delete, foreign_key.on_update);
+            self.find_model_mut(&table.name)
+                .expect("owning model to exist")
+                .add_relation_field(Field::new_relation(
+                    names.field,
+                    referenced_name.to_string(),
+                    is_required,
+                    false,
+                    Some(relation),
+                ));
+            self.find_model_mut(referenced_name)
+                .expect("referenced model to exist")
+                .add_relation_field(Field::new_relation(
+                    names.back_field,
+                    table.name.clone(),
+                    false,
+                    !is_one_to_one,
+                    names
+                        .relation
+                        .map(|relation_name| Relation::new(Some(relation_name), None, None)),
+                ));
+        }
+    }
+
+    /// Renames every model and field following the naming policy.
+    fn apply_naming(&mut self, naming: NamingPolicy) {
+        if naming == NamingPolicy::Database {
+            return;
+        }
+        let model_names: HashMap<String, String> = self
+            .models
+            .iter()
+            .map(|model| {
+                let name = model.get_name().to_string();
+                let renamed = naming.model_name(&name);
+                (name, renamed)
+            })
+            .collect();
+        let field_renames: HashMap<String, HashMap<String, String>> = self
+            .models
+            .iter()
+            .map(|model| {
+                (
+                    model.get_name().to_string(),
+                    model.field_renames(naming, &model_names),
+                )
+            })
+            .collect();
+        for model in self.models.iter_mut() {
+            model.apply_naming(naming, &model_names, &field_renames);
+        }
+    }
+
     pub fn as_text(&self) -> String {
+        format!("{}\n{}", self.config_text(), self.models_text())
+    }
+
+    /// The generator and datasource blocks.
+    pub fn config_text(&self) -> String {
         let mut text = String::new();
-        text.push_str(&self.generator.as_text());
-        text.push_str("\n\n");
+        for generator in &self.generators {
+            text.push_str(&generator.as_text());
+            text.push_str("\n\n");
+        }
         text.push_str(&self.datasource.as_text());
-        text.push_str("\n\n");
+        text.push('\n');
+        text
+    }
+
+    /// The model and enum blocks.
+    pub fn models_text(&self) -> String {
+        let mut text = String::new();
         for model in &self.models {
             text.push_str(&model.as_text());
-            text.push_str("\n");
+            text.push('\n');
+        }
+        for enumeration in &self.enums {
+            text.push_str(&enumeration.as_text());
+            text.push('\n');
         }
         text
     }
 
+    /// Splits the schema into the files of a schema folder, by file name. The first holds the
+    /// generators and datasource, and the others the models of a group, a model on its own or
+    /// the enums.
+    pub fn split(&self, groups: &[ModelGroup]) -> Vec<(String, PrismaSchema)> {
+        let part = |models: Vec<Model>, enums: Vec<Enum>| PrismaSchema {
+            generators: self.generators.clone(),
+            datasource: self.datasource.clone(),
+            models,
+            enums,
+        };
+        let mut files = vec![(String::from(SCHEMA_FILE), part(vec![], vec![]))];
+        for group in groups {
+            let file_name = format!("{}.prisma", group.name);
+            let models: Vec<Model> = self
+                .models
+                .iter()
+                .filter(|model| model_file_name(model, groups) == file_name)
+                .cloned()
+                .collect();
+            if !models.is_empty() {
+                files.push((file_name, part(models, vec![])));
+            }
+        }
+        for model in self.models.iter() {
+            if !groups.iter().any(|group| group.contains(model)) {
+                files.push((
+                    model_file_name(model, groups),
+                    part(vec![model.clone()], vec![]),
+                ));
+            }
+        }
+        if !self.enums.is_empty() {
+            files.push((String::from(ENUMS_FILE), part(vec![], self.enums.clone())));
+        }
+        files
+    }
+
+    /// Reads the files of a schema folder back into one schema.
+    pub fn from_files(files: &[SchemaFile]) -> PrismaSchema {
+        let mut schema = PrismaSchema::new();
+        schema.generators.clear();
+        for block in files.iter().flat_map(|file| file.blocks.iter()) {
+            match block.kind {
+                BlockKind::Generator => schema.generators.push(Generator::from_block(block)),
+                BlockKind::Datasource => schema.datasource = Datasource::from_block(block),
+                BlockKind::Model => schema.add_model(Model::from_block(block)),
+                BlockKind::Enum => schema.add_enum(Enum::from_block(block)),
+                BlockKind::View | BlockKind::Type => {}
+            }
+        }
+        schema
+    }
+
     pub fn load(&self, full_path: &String) -> String {
         let file = File::open(full_path).unwrap();
         let mut contents = String::new();
         for line in BufReader::new(file).lines() {
             contents.push_str(&line.unwrap());
-            contents.push_str("\n");
+            contents.push('\n');
         }
         contents
     }
@@ -67,19 +334,116 @@ impl PrismaSchema {
 
 impl PartialEq for PrismaSchema {
     fn eq(&self, other: &Self) -> bool {
-        self.generator == other.generator
+        self.generators == other.generators
             && self.datasource == other.datasource
             && self.models == other.models
+            && self.enums == other.enums
     }
 }
 
-impl From<&Vec<Table>> for PrismaSchema {
-    fn from(tables: &Vec<Table>) -> Self {
+impl PrismaSchema {
+    /// Translate the tables, naming models and fields following the naming policy.
+    pub fn from_tables(tables: &[Table], naming: NamingPolicy) -> PrismaSchema {
         let mut prisma_schema = PrismaSchema::new();
+        if let Some(table) = tables.first() {
+            prisma_schema.datasource = Datasource::for_dialect(table.dialect);
+        }
         for table in tables {
             let model = Model::from(table);
             prisma_schema.add_model(model);
+            for enumeration in Enum::from_table(table) {
+                prisma_schema.add_enum(enumeration);
+            }
         }
+        prisma_schema.add_relations(tables);
+        prisma_schema.apply_naming(naming);
         prisma_schema
     }
+
+    /// Read the models back into the tables they were generated from.
+    pub fn to_tables(&self) -> Result<Vec<Table>> {
+        tables::schema_tables(self)
+    }
+}
+
+impl From<&SchemaFile> for PrismaSchema {
+    /// The schema's generator, datasource, models and enums. Views and composite types are left
+    /// out, as nothing is generated for them.
+    fn from(file: &SchemaFile) -> Self {
+        PrismaSchema::from_files(std::slice::from_ref(file))
+    }
+}
+
+impl From<&Vec<Table>> for PrismaSchema {
+    fn from(tables: &Vec<Table>) -> Self {
+        PrismaSchema::from_tables(tables, NamingPolicy::Database)
+    }
+}
+
+/// The relation name and the field names on either side of a foreign key, following the naming
+/// used by `prisma db pull`.
+struct RelationNames {
+    relation: Option<String>,
+    field: String,
+    back_field: String,
+}
+
+impl RelationNames {
+    fn new(table: &Table, foreign_key: &ForeignKey, ambiguous: bool) -> RelationNames {
+        let referenced_name = &foreign_key.referenced_table_name;
+        let is_self_relation = &table.name == referenced_name;
+        let back_prefix = if is_self_relation { "other_" } else { "" };
+        if ambiguous {
+            let relation = format!(
+                "{}_{}To{}",
+                table.name,
+                foreign_key.column_names.join("_"),
+                referenced_name
+            );
+            return RelationNames {
+                field: format!("{}_{}", referenced_name, relation),
+                back_field: format!("{}{}_{}", back_prefix, table.name, relation),
+                relation: Some(relation),
+            };
+        }
+        RelationNames {
+            relation: match is_self_relation {
+                true => Some(format!("{}To{}", table.name, referenced_name)),
+                false => None,
+            },
+            field: referenced_name.clone(),
+            back_field: format!("{}{}", back_prefix, table.name),
+        }
+    }
+}
+
+/// Whether the columns together identify a row, making a relation over them one-to-one.
+fn is_unique_columns(table: &Table, column_names: &[String]) -> bool {
+    let mut sorted_column_names = column_names.to_vec();
+    sorted_column_names.sort();
+    let mut primary_key_columns: Vec<String> = table
+        .description
+        .iter()
+        .filter(|description| description.key.contains("PRI"))
+        .map(|description| description.field.clone())
+        .collect();
+    primary_key_columns.sort();
+    if primary_key_columns == sorted_column_names {
+        return true;
+    }
+    if column_names.len() == 1
+        && table.description.iter().any(|description| {
+            description.field == column_names[0] && description.key.contains("UNI")
+        })
+    {
+        return true;
+    }
+    table.keys.keys.iter().any(|key| match key {
+        Key::Unique(unique_keys) => unique_keys.iter().any(|unique_key| {
+            let mut unique_column_names = unique_key.column_names.clone();
+            unique_column_names.sort();
+            unique_column_names == sorted_column_names
+        }),
+        _ => false,
+    })
 }
@@ -0,0 +1,408 @@
+use crate::remotes::sql::{
+    describe_key, key_information_from_rows, Description, Dialect, ForeignKeyRow, IndexRow,
+    ReferentialAction, Table, TableKeys,
+};
+use crate::translators::prisma::{
+    enumeration::Enum,
+    field::Field,
+    model::{base_type, Model},
+    schema::PrismaSchema,
+};
+use anyhow::{bail, Result};
+
+/// Reads the schema's models back into the tables they were generated from. Names Prisma
+/// doesn't keep, such as the constraint name of a foreign key without a `map`, take the names
+/// Prisma gives them by default.
+pub fn schema_tables(schema: &PrismaSchema) -> Result<Vec<Table>> {
+    let dialect = match schema.get_datasource().dialect() {
+        Some(dialect) => dialect,
+        None => bail!(
+            "the {} provider has no tables to read back",
+            schema.get_datasource().get_provider()
+        ),
+    };
+    Ok(schema
+        .get_models()
+        .iter()
+        .map(|model| model_table(schema, model, dialect))
+        .collect())
+}
+
+/// A directive's columns and constraint name, such as `@@index([a, b], map: "c")`.
+struct Directive {
+    kind: String,
+    columns: Vec<String>,
+    map: Option<String>,
+}
+
+impl Directive {
+    fn parse(directive: &str) -> Option<Directive> {
+        let directive = directive.trim();
+        let start = directive.find('[')?;
+        let end = directive.find(']')?;
+        let columns = directive[start + 1..end]
+            .split(',')
+            .map(|column| column.split('(').next().unwrap_or_default().trim())
+            .filter(|column| !column.is_empty())
+            .map(str::to_string)
+            .collect();
+        let map = directive[end..]
+            .split("map:")
+            .nth(1)
+            .and_then(|map| map.trim().strip_prefix('"'))
+            .and_then(|map| map.split('"').next())
+            .map(str::to_string);
+        Some(Directive {
+            kind: directive[..directive.find('(')?].to_string(),
+            columns,
+            map,
+        })
+    }
+}
+
+fn model_table(schema: &PrismaSchema, model: &Model, dialect: Dialect) -> Table {
+    let table_name = model.table_name().to_string();
+    let find_model = |name: &str| {
+        schema
+            .get_models()
+            .iter()
+            .find(|model| model.get_name() == name)
+    };
+    let directives: Vec<Directive> = model
+        .directive_lines()
+        .iter()
+        .filter_map(|directive| Directive::parse(directive))
+        .collect();
+    let columns: Vec<&Field> = model
+        .get_fields()
+        .iter()
+        .filter(|field| find_model(base_type(&field.field_type)).is_none())
+        .collect();
+
+    let mut indexes: Vec<IndexRow> = vec![];
+    let mut push_index = |name: &str, is_unique: bool, is_primary: bool, fields: &[String]| {
+        for (position, field) in fields.iter().enumerate() {
+            indexes.push((
+                name.to_string(),
+                is_unique,
+                is_primary,
+                Some(column_name(model, field)),
+                position as i64 + 1,
+            ));
+        }
+    };
+    let primary_key = match directives.iter().find(|directive| directive.kind == "@@id") {
+        Some(directive) => directive.columns.clone(),
+        None => columns
+            .iter()
+            .filter(|field| field.is_id)
+            .map(|field| field.name.clone())
+            .collect(),
+    };
+    push_index("PRIMARY", true, true, &primary_key);
+    for field in columns.iter() {
+        if let Some(unique) = &field.unique {
+            let name = unique.map.clone().unwrap_or_else(|| {
+                default_constraint_name(&table_name, &[column_name(model, &field.name)], "key")
+            });
+            push_index(&name, true, false, std::slice::from_ref(&field.name));
+        }
+    }
+    for directive in directives.iter() {
+        let (is_unique, suffix) = match directive.kind.as_str() {
+            "@@unique" => (true, "key"),
+            "@@index" => (false, "idx"),
+            _ => continue,
+        };
+        let name = directive.map.clone().unwrap_or_else(|| {
+            let column_names: Vec<String> = directive
+                .columns
+                .iter()
+                .map(|field| column_name(model, field))
+                .collect();
+            default_constraint_name(&table_name, &column_names, suffix)
+        });
+        push_index(&name, is_unique, false, &directive.columns);
+    }
+
+    let mut foreign_keys: Vec<ForeignKeyRow> = vec![];
+    for field in model.get_fields() {
+        let (Some(relation), Some(referenced_model)) =
+            (&field.relation, find_model(base_type(&field.field_type)))
+        else {
+            continue;
+        };
+        let (Some(fields), Some(references)) = (relation.get_fields(), relation.get_references())
+        else {
+            continue;
+        };
+        let column_names: Vec<String> = fields
+            .iter()
+            .map(|field| column_name(model, field))
+            .collect();
+        let name = relation
+            .get_map()
+            .map(str::to_string)
+            .unwrap_or_else(|| default_constraint_name(&table_name, &column_names, "fkey"));
+        let (on_delete, on_update) = relation.referential_actions();
+        let action = |action: Option<ReferentialAction>| {
+            action
+                .map(|action| action.as_string().to_string())
+                .unwrap_or_default()
+        };
+        for (position, (column, reference)) in column_names.iter().zip(references).enumerate() {
+            foreign_keys.push((
+                name.clone(),
+                column.clone(),
+                position as i64 + 1,
+                referenced_model.table_name().to_string(),
+                column_name(referenced_model, reference),
+                action(on_update),
+                action(on_delete),
+            ));
+        }
+    }
+
+    let enums = schema.get_enums();
+    let description = columns
+        .iter()
+        .map(|field| {
+            let name = column_name(model, &field.name);
+            let enumeration = enums
+                .iter()
+                .find(|enumeration| enumeration.get_name() == base_type(&field.field_type));
+            let type_ = column_type(field, dialect, enumeration);
+            let (default, extra) = column_default(field, dialect, enumeration, &type_);
+            Description {
+                key: describe_key(&name, &indexes),
+                field: name,
+                type_,
+                null: String::from(if field.is_required || field.is_array {
+                    "NO"
+                } else {
+                    "YES"
+                }),
+                default,
+                extra,
+                comment: comment(&field.documentation),
+            }
+        })
+        .collect();
+    Table {
+        name: table_name,
+        dialect,
+        description,
+        keys: TableKeys::from(key_information_from_rows(&indexes, &foreign_keys)),
+        comment: comment(model.get_documentation()),
+    }
+}
+
+/// The database column behind one of the model's fields, from its @map if it has one.
+fn column_name(model: &Model, field_name: &str) -> String {
+    model
+        .get_fields()
+        .iter()
+        .find(|field| field.name == field_name)
+        .and_then(|field| field.map.clone())
+        .unwrap_or_else(|| field_name.to_string())
+}
+
+/// The name Prisma gives a constraint without a `map`, such as `post_author_id_fkey`.
+fn default_constraint_name(table_name: &str, column_names: &[String], suffix: &str) -> String {
+    format!("{}_{}_{}", table_name, column_names.join("_"), suffix)
+}
+
+fn comment(documentation: &[String]) -> Option<String> {
+    Some(documentation.join("\n")).filter(|comment| !comment.is_empty())
+}
+
+/// The native type annotation's name and arguments, such as `Decimal` and `10,2` for
+/// `@db.Decimal(10, 2)`.
+fn annotation(field: &Field) -> Option<(&str, Option<String>)> {
+    let annotation = field.db_type_annotation.as_deref()?.strip_prefix("@db.")?;
+    Some(match annotation.find('(') {
+        Some(start) => (
+            &annotation[..start],
+            Some(annotation[start + 1..annotation.len() - 1].replace(' ', "")),
+        ),
+        None => (annotation, None),
+    })
+}
+
+/// The column type as the database prints it, the reverse of the type mapping fields are
+/// written with.
+fn column_type(field: &Field, dialect: Dialect, enumeration: Option<&Enum>) -> String {
+    let base = base_type(&field.field_type);
+    if let Some(unsupported) = base
+        .strip_prefix("Unsupported(\"")
+        .and_then(|base| base.strip_suffix("\")"))
+    {
+        return unsupported.to_string();
+    }
+    if let Some(enumeration) = enumeration {
+        if dialect != Dialect::MySql {
+            return enumeration.get_name().to_string();
+        }
+        let values: Vec<String> = enumeration
+            .get_values()
+            .iter()
+            .map(|value| {
+                let value = value.map.as_ref().unwrap_or(&value.name);
+                format!("'{}'", value.replace('\'', "''"))
+            })
+            .collect();
+        return format!("enum({})", values.join(","));
+    }
+    let annotation = annotation(field);
+    let sized = |name: &str, arguments: &Option<String>| match arguments {
+        Some(arguments) => format!("{}({})", name, arguments),
+        None => name.to_string(),
+    };
+    match dialect {
+        Dialect::MySql => match annotation {
+            Some((name, _)) if name.starts_with("Unsigned") => {
+                format!("{} unsigned", name["Unsigned".len()..].to_lowercase())
+            }
+            // MySQL leaves a precision of 0 off its time types.
+            Some((name @ ("DateTime" | "Timestamp" | "Time"), Some(arguments)))
+                if arguments == "0" =>
+            {
+                name.to_lowercase()
+            }
+            Some((name, arguments)) => sized(&name.to_lowercase(), &arguments),
+            None => String::from(match base {
+                "Boolean" => "tinyint(1)",
+                "Int" => "int",
+                "BigInt" => "bigint",
+                "Float" => "double",
+                "Decimal" => "decimal(65,30)",
+                "DateTime" => "datetime(3)",
+                "String" => "varchar(191)",
+                "Bytes" => "longblob",
+                "Json" => "json",
+                _ => return base.to_lowercase(),
+            }),
+        },
+        Dialect::Postgres => {
+            let column_type = match annotation {
+                Some((name, arguments)) => {
+                    let (name, suffix) = match name {
+                        "Decimal" => ("numeric", ""),
+                        "VarChar" => ("character varying", ""),
+                        "Char" => ("character", ""),
+                        "VarBit" => ("bit varying", ""),
+                        "Timestamp" => ("timestamp", " without time zone"),
+                        "Timestamptz" => ("timestamp", " with time zone"),
+                        "Time" => ("time", " without time zone"),
+                        "Timetz" => ("time", " with time zone"),
+                        name => return sized(&name.to_lowercase(), &arguments) + list(field),
+                    };
+                    format!("{}{}", sized(name, &arguments), suffix)
+                }
+                None => String::from(match base {
+                    "Int" => "integer",
+                    "BigInt" => "bigint",
+                    "Float" => "double precision",
+                    "Decimal" => "numeric(65,30)",
+                    "String" => "text",
+                    "Boolean" => "boolean",
+                    "DateTime" => "timestamp(3) without time zone",
+                    "Json" => "jsonb",
+                    "Bytes" => "bytea",
+                    _ => base,
+                }),
+            };
+            column_type + list(field)
+        }
+        Dialect::Sqlite => String::from(match base {
+            "Int" => "INTEGER",
+            "BigInt" => "BIGINT",
+            "Float" => "REAL",
+            "Decimal" => "DECIMAL",
+            "String" => "TEXT",
+            "Boolean" => "BOOLEAN",
+            "DateTime" => "DATETIME",
+            "Json" => "JSON",
+            "Bytes" => "BLOB",
+            _ => return base.to_uppercase(),
+        }),
+    }
+}
+
+fn list(field: &Field) -> &'static str {
+    match field.is_array {
+        true => "[]",
+        false => "",
+    }
+}
+
+/// The column's default and extra as `DESCRIBE` would show them, the reverse of the Prisma
+/// default the field was written with.
+fn column_default(
+    field: &Field,
+    dialect: Dialect,
+    enumeration: Option<&Enum>,
+    column_type: &str,
+) -> (Option<String>, String) {
+    // MySQL keeps the precision of the current time, such as `CURRENT_TIMESTAMP(3)`.
+    let now = match (dialect, column_type.find('(')) {
+        (Dialect::MySql, Some(start))
+            if column_type.starts_with("datetime") || column_type.starts_with("timestamp") =>
+        {
+            format!("CURRENT_TIMESTAMP{}", &column_type[start..])
+        }
+        _ => String::from("CURRENT_TIMESTAMP"),
+    };
+    let mut extra: Vec<String> = vec![];
+    let default = match field.default.as_deref() {
+        None => None,
+        Some("autoincrement()") => {
+            extra.push(String::from("auto_increment"));
+            None
+        }
+        Some("now()") => {
+            extra.push(String::from("DEFAULT_GENERATED"));
+            Some(now.clone())
+        }
+        Some(default) if default.starts_with("dbgenerated(") => {
+            extra.push(String::from("DEFAULT_GENERATED"));
+            Some(unquote(&default["dbgenerated(".len()..default.len() - 1]))
+        }
+        Some(boolean @ ("true" | "false")) if dialect != Dialect::Postgres => {
+            Some(String::from(if boolean == "true" { "1" } else { "0" }))
+        }
+        Some(default) if default.starts_with('"') => Some(unquote(default)),
+        Some(default) => Some(
+            enumeration
+                .and_then(|enumeration| {
+                    enumeration
+                        .get_values()
+                        .iter()
+                        .find(|value| value.name == default)
+                })
+                .map(|value| value.map.clone().unwrap_or_else(|| value.name.clone()))
+                .unwrap_or_else(|| default.to_string()),
+        ),
+    };
+    if field.is_updated_at {
+        extra.push(format!("on update {}", now));
+    }
+    (default, extra.join(" "))
+}
+
+/// A Prisma string literal's value, with its quotes and escapes taken off.
+fn unquote(literal: &str) -> String {
+    let literal = literal
+        .strip_prefix('"')
+        .and_then(|literal| literal.strip_suffix('"'))
+        .unwrap_or(literal);
+    let mut value = String::new();
+    let mut characters = literal.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' => value.extend(characters.next()),
+            character => value.push(character),
+        }
+    }
+    value
+}
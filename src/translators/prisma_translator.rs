@@ -1,25 +1,43 @@
+use crate::functionality::structure::DiskMapping;
 use crate::remotes::sql::Table;
 use crate::translators::behaviour::TranslatorBehaviour;
 use crate::translators::prisma::{
-    data_source::Datasource, generator::Generator, model::Model, schema::PrismaSchema,
+    diff::SchemaDiff,
+    merge::{merge, merge_folder},
+    naming::NamingPolicy,
+    options::{ModelGroup, PrismaLayout, PrismaOptions},
+    parser::SchemaFile,
+    schema::{PrismaSchema, SCHEMA_FILE},
 };
-use anyhow::Result;
-use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The folder a schema folder is written to beside the mapped path, when not merging.
+pub const OUTPUT_FOLDER: &str = "mysql_output_from_db";
+/// The files the last write put in the output folder, so only those are ever removed.
+const OUTPUT_MANIFEST: &str = ".written";
 
 pub struct PrismaTranslator {
     pub path: String,
     pub disk_schema: Option<PrismaSchema>,
     pub db_schema: Option<PrismaSchema>,
+    pub naming: NamingPolicy,
+    /// Merge into the schema at the path instead of writing a separate file beside it.
+    pub merge: bool,
+    pub options: PrismaOptions,
 }
 
-impl TranslatorBehaviour<PrismaSchema> for PrismaTranslator {
-    fn get_translation(&self, database: &Vec<Table>) -> PrismaSchema {
-        PrismaSchema::from(database)
+impl TranslatorBehaviour for PrismaTranslator {
+    type Translation = PrismaSchema;
+
+    fn get_translation(&self, database: &[Table]) -> PrismaSchema {
+        let mut schema = PrismaSchema::from_tables(database, self.naming);
+        schema.apply_options(&self.options);
+        schema
     }
 
-    fn load_from_database(&mut self, database: &Vec<Table>) {
+    fn load_from_database(&mut self, database: &[Table]) {
         self.db_schema = Some(self.get_translation(database));
     }
 
@@ -31,12 +49,18 @@ impl TranslatorBehaviour<PrismaSchema> for PrismaTranslator {
         Ok(())
     }
 
-    fn write_to_disk(&self, database: &Vec<Table>) -> Result<()> {
+    fn write_to_disk(&self, database: &[Table]) -> Result<()> {
+        if let PrismaLayout::Folder { groups } = &self.options.layout {
+            return self.write_folder(database, groups);
+        }
+        if self.merge {
+            return self.merge_to_disk(database);
+        }
         let mut output_path_buf = PathBuf::new();
         output_path_buf.push(&self.path);
         let output_path_buf = output_path_buf.with_file_name("mysql_output_from_db.prisma");
         let output_path = output_path_buf.as_path();
-        let schema = PrismaSchema::from(database);
+        let schema = self.get_translation(database);
         fs::write(
             output_path.to_str().expect("output path to exist"),
             schema.as_text(),
@@ -45,76 +69,158 @@ impl TranslatorBehaviour<PrismaSchema> for PrismaTranslator {
     }
 
     fn get_string(&self) -> String {
-        if self.disk_schema.is_none() && self.db_schema.is_none() {
-            return String::new();
-        }
         let mut resp = String::new();
-        if self.disk_schema.is_some() {
+        if let Some(disk_schema) = &self.disk_schema {
             resp.push_str("disk schema:\n\n");
-            resp.push_str(&self.disk_schema.as_ref().unwrap().as_text());
+            resp.push_str(&disk_schema.as_text());
             resp.push_str("\n\n");
         }
-        if self.db_schema.is_some() {
+        if let Some(diff) = self.diff() {
+            resp.push_str("changes in the database:\n\n");
+            resp.push_str(&diff.as_text());
+        } else if let Some(db_schema) = &self.db_schema {
             resp.push_str("db schema:\n\n");
-            resp.push_str(&self.db_schema.as_ref().unwrap().as_text());
+            resp.push_str(&db_schema.as_text());
             resp.push_str("\n\n");
         }
         resp
     }
+
+    /// Read the schema loaded from disk back into tables.
+    fn to_tables(&self) -> Result<Vec<Table>> {
+        match &self.disk_schema {
+            Some(disk_schema) => disk_schema.to_tables(),
+            None => bail!("no Prisma schema has been loaded from {}", self.path),
+        }
+    }
+
+    /// The schema on disk is shown with the database's changes to it.
+    fn compares_with_database(&self) -> bool {
+        true
+    }
+
+    fn warnings(&self, database: &[Table]) -> Vec<String> {
+        self.get_translation(database).warnings()
+    }
 }
 
 impl PrismaTranslator {
-    pub fn parse_from_disk(&self) -> Result<PrismaSchema> {
-        let file = File::open(self.path.clone())?;
-        let mut generator_str = String::new();
-        let mut data_source_str = String::new();
-        let mut models_str = String::new();
-
-        enum PushingTo {
-            Generator,
-            Datasource,
-            Models,
+    /// The translator for a mapping's schema, written with the mapping's naming and options.
+    pub fn from_mapping(mapping: &DiskMapping) -> PrismaTranslator {
+        PrismaTranslator {
+            path: mapping.path.clone(),
+            disk_schema: None,
+            db_schema: None,
+            naming: mapping.naming,
+            merge: mapping.merge,
+            options: mapping.prisma.clone(),
         }
+    }
+
+    /// Merge the database's schema into the schema at the path, keeping the hand edits made to
+    /// it. A path with nothing at it yet gets the database's schema as is.
+    pub fn merge_to_disk(&self, database: &[Table]) -> Result<()> {
+        let schema = self.get_translation(database);
+        let text = match fs::read_to_string(&self.path) {
+            Ok(disk_text) => merge(&disk_text, &schema)
+                .with_context(|| format!("couldn't merge into {}", self.path))?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => schema.as_text(),
+            Err(error) => return Err(error.into()),
+        };
+        fs::write(&self.path, text)?;
+        Ok(())
+    }
+
+    /// How the database's schema differs from the one on disk, once both are loaded.
+    pub fn diff(&self) -> Option<SchemaDiff> {
+        Some(SchemaDiff::between(
+            self.disk_schema.as_ref()?,
+            self.db_schema.as_ref()?,
+        ))
+    }
 
-        let mut pushing_to: PushingTo = PushingTo::Generator;
-
-        for line in BufReader::new(file).lines() {
-            let current_line = line.unwrap();
-            if current_line.starts_with("generator") {
-                pushing_to = PushingTo::Generator;
-            } else if current_line.starts_with("datasource") {
-                pushing_to = PushingTo::Datasource;
-            } else if current_line.starts_with("model") {
-                pushing_to = PushingTo::Models;
-            } else {
-                pushing_to = pushing_to
+    /// Write the schema as a schema folder. Like a single schema file, it's written to a
+    /// folder of its own beside the path, `mysql_output_from_db`, unless merging into the folder
+    /// at the path, where the whole folder is merged with the database's schema at once. Files
+    /// left over from models that were dropped are only removed if this wrote them, as listed in
+    /// the folder's manifest.
+    pub fn write_folder(&self, database: &[Table], groups: &[ModelGroup]) -> Result<()> {
+        let schema = self.get_translation(database);
+        if self.merge {
+            let folder = PathBuf::from(&self.path);
+            fs::create_dir_all(&folder)?;
+            let mut files: Vec<(String, String)> = vec![];
+            for path in schema_files(&folder)? {
+                let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+                files.push((file_name, fs::read_to_string(&path)?));
             }
-            match pushing_to {
-                PushingTo::Generator => {
-                    generator_str.push_str(&current_line);
-                    generator_str.push_str("\n");
-                }
-                PushingTo::Datasource => {
-                    data_source_str.push_str(&current_line);
-                    data_source_str.push_str("\n");
+            for (file_name, text) in merge_folder(&files, &schema, groups)
+                .with_context(|| format!("couldn't merge into {}", folder.display()))?
+            {
+                fs::write(folder.join(file_name), format!("{}\n", text.trim_end()))?;
+            }
+            return Ok(());
+        }
+        let folder = Path::new(&self.path).with_file_name(OUTPUT_FOLDER);
+        fs::create_dir_all(&folder)?;
+        let mut written: Vec<String> = vec![];
+        for (file_name, part) in schema.split(groups) {
+            let text = match file_name == SCHEMA_FILE {
+                true => part.config_text(),
+                false => part.models_text(),
+            };
+            fs::write(folder.join(&file_name), format!("{}\n", text.trim_end()))?;
+            written.push(file_name);
+        }
+        let manifest = folder.join(OUTPUT_MANIFEST);
+        if let Ok(previous) = fs::read_to_string(&manifest) {
+            for file_name in previous.lines() {
+                if written.iter().any(|written| written == file_name)
+                    || !file_name.ends_with(".prisma")
+                {
+                    continue;
                 }
-                PushingTo::Models => {
-                    models_str.push_str(&current_line);
-                    models_str.push_str("\n");
+                match fs::remove_file(folder.join(file_name)) {
+                    Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                        return Err(error.into())
+                    }
+                    _ => {}
                 }
             }
         }
-        let generator = Generator::parse_from_disk(&generator_str);
-        let datasource = Datasource::parse_from_disk(&data_source_str);
-        let mut models: Vec<Model> = vec![];
-        let models_strs: Vec<&str> = models_str.split("model ").collect();
-        for curr_model_str in models_strs {
-            let model = Model::parse_from_disk(curr_model_str);
-            if model.is_none() {
-                continue;
-            }
-            models.push(model.unwrap());
+        fs::write(&manifest, written.join("\n") + "\n")?;
+        Ok(())
+    }
+
+    pub fn parse_from_disk(&self) -> Result<PrismaSchema> {
+        let path = Path::new(&self.path);
+        let paths = match path.is_dir() {
+            true => schema_files(path)?,
+            false => vec![path.to_path_buf()],
+        };
+        let mut files: Vec<SchemaFile> = vec![];
+        for path in paths {
+            let text = fs::read_to_string(&path)?;
+            let file =
+                SchemaFile::parse(&text).with_context(|| format!("in {}", path.display()))?;
+            files.push(file);
+        }
+        Ok(PrismaSchema::from_files(&files))
+    }
+}
+
+/// The `.prisma` files in a schema folder, in name order.
+fn schema_files(folder: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = vec![];
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "prisma")
+        {
+            paths.push(path);
         }
-        Ok(PrismaSchema::build(generator, datasource, models))
     }
+    paths.sort();
+    Ok(paths)
 }
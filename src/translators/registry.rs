@@ -0,0 +1,71 @@
+use crate::functionality::structure::{AcceptedFormat, DiskMapping};
+use crate::translators::{
+    behaviour::Translator, json_translator::JsonTranslator, prisma_translator::PrismaTranslator,
+};
+use anyhow::{anyhow, Result};
+
+/// Builds a format's translator for a mapping, with the mapping's options.
+pub type TranslatorConstructor = fn(&DiskMapping) -> Box<dyn Translator>;
+
+/// The translators by format name. A session keeps one, which mappings, `--convert` and the
+/// menus take their formats from.
+#[derive(Clone)]
+pub struct TranslatorRegistry {
+    constructors: Vec<(&'static str, TranslatorConstructor)>,
+}
+
+impl TranslatorRegistry {
+    pub fn new() -> TranslatorRegistry {
+        TranslatorRegistry {
+            constructors: vec![],
+        }
+    }
+
+    /// Register a format's translator, replacing any already registered under the name.
+    pub fn register(&mut self, format: &'static str, constructor: TranslatorConstructor) {
+        self.constructors.retain(|(name, _)| *name != format);
+        self.constructors.push((format, constructor));
+    }
+
+    /// The registered formats, in the order they were registered.
+    pub fn formats(&self) -> Vec<&'static str> {
+        self.constructors.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// The registered format with the name, in any case.
+    pub fn format(&self, format: &str) -> Option<AcceptedFormat> {
+        self.constructors
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(format))
+            .map(|(name, _)| AcceptedFormat::from_name(name))
+    }
+
+    /// The format's translator for the mapping.
+    pub fn translator(&self, format: &str, mapping: &DiskMapping) -> Result<Box<dyn Translator>> {
+        self.constructors
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(format))
+            .map(|(_, constructor)| constructor(mapping))
+            .ok_or_else(|| {
+                anyhow!(
+                    "no translator is registered for {}, expected one of {}",
+                    format,
+                    self.formats().join(", ")
+                )
+            })
+    }
+}
+
+impl Default for TranslatorRegistry {
+    /// Every translator that comes with mysql translate.
+    fn default() -> Self {
+        let mut registry = TranslatorRegistry::new();
+        registry.register("json", |mapping| {
+            Box::new(JsonTranslator::from_mapping(mapping))
+        });
+        registry.register("prisma", |mapping| {
+            Box::new(PrismaTranslator::from_mapping(mapping))
+        });
+        registry
+    }
+}
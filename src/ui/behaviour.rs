@@ -24,6 +24,10 @@ pub trait UI {
     /// the schemas without writing to disk
     fn view_tables_from_database(&self) -> Result<()>;
 
+    /// Allow the user to convert one of a database's local schemas into another format, without
+    /// connecting to the database
+    fn convert_schema(&self) -> Result<()>;
+
     /// Choose a database to edit the settings (name, url) for
     fn edit_databases(&mut self) -> Result<()>;
 
@@ -1,5 +1,18 @@
 use crate::{
-    functionality::{database, session::Session, structure::AcceptedFormat},
+    functionality::{
+        database,
+        session::Session,
+        structure::{AcceptedFormat, RemoteSource},
+    },
+    translators::{
+        json::shape::JsonShape,
+        ordering::TableOrder,
+        prisma::{
+            generator::Generator,
+            naming::NamingPolicy,
+            options::{DatasourceOptions, PrismaLayout, PrismaOptions},
+        },
+    },
     ui::behaviour::UI,
 };
 use anyhow::Result;
@@ -9,13 +22,13 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetForegroundColor},
     terminal::{Clear, ClearType, SetTitle},
 };
-use dialoguer::Select;
+use dialoguer::{Input, Select};
 use std::cell::RefCell;
 use std::process::exit;
 
 /// TODO: Remove view tables from disk vs view tables from database
 /// TODO: Add view diff between database and disk
-static MAIN_MENU_ITEMS: [&str; 9] = [
+static MAIN_MENU_ITEMS: [&str; 10] = [
     "display databases",
     "sync databases",
     "sync one",
@@ -23,6 +36,7 @@ static MAIN_MENU_ITEMS: [&str; 9] = [
     "edit databases",
     "view tables from disk",
     "view tables from database",
+    "convert a schema",
     "clear terminal",
     "exit",
 ];
@@ -59,6 +73,13 @@ impl TerminalUI {
         .expect("print to work");
     }
 
+    /// Prints each warning from a write
+    fn warning_messages(&self, warnings: &[String]) {
+        for warning in warnings {
+            self.sad_message(&format!("warning: {}", warning));
+        }
+    }
+
     /// Prints a nice other colour for prompts (currently dark magenta)
     fn prompt_message(&self, message: &str) {
         execute!(
@@ -101,6 +122,118 @@ impl TerminalUI {
         self.happy_message(GOODBYE_STRING);
     }
 
+    /// Prompts for text, offering the current value as the default. Empty input is allowed.
+    fn prompt_with_default(&self, prompt: &str, default: &str) -> Result<String> {
+        let input: String = Input::new()
+            .with_prompt(prompt)
+            .default(default.to_string())
+            .allow_empty(true)
+            .interact_text()?;
+        Ok(input.trim().to_string())
+    }
+
+    /// Prompts for a comma separated list.
+    fn prompt_for_list(&self, prompt: &str, default: &[String]) -> Result<Vec<String>> {
+        let input = self.prompt_with_default(prompt, &default.join(", "))?;
+        Ok(input
+            .split(',')
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .collect())
+    }
+
+    /// The formats the session has translators for, as menu options.
+    fn format_options(&self) -> Vec<String> {
+        self.session
+            .borrow()
+            .registry
+            .formats()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Walks through the datasource and generator blocks written for a Prisma mapping.
+    fn edit_prisma_options(&mut self, database_index: usize) -> Result<()> {
+        let current = self.session.borrow().databases[database_index]
+            .disk_mappings
+            .iter()
+            .find(|mapping| mapping.format == AcceptedFormat::Prisma)
+            .map(|mapping| mapping.prisma.clone())
+            .unwrap_or_default();
+        let datasource = &current.datasource;
+        let name = self.prompt_with_default("datasource name", &datasource.name)?;
+        let url_env = self.prompt_with_default("url environment variable", &datasource.url_env)?;
+        let shadow_database_url_env = self.prompt_with_default(
+            "shadow database url environment variable (empty for none)",
+            datasource
+                .shadow_database_url_env
+                .as_deref()
+                .unwrap_or_default(),
+        )?;
+        self.prompt_message("how are relations enforced?");
+        let relation_modes = ["foreignKeys", "prisma"];
+        let relation_mode = Select::new()
+            .items(&relation_modes)
+            .default(match datasource.relation_mode.as_deref() {
+                Some("prisma") => 1,
+                _ => 0,
+            })
+            .interact()?;
+        let mut options = PrismaOptions {
+            datasource: DatasourceOptions {
+                name,
+                url_env,
+                relation_mode: (relation_mode == 1).then(|| String::from("prisma")),
+                shadow_database_url_env: Some(shadow_database_url_env)
+                    .filter(|env| !env.is_empty()),
+            },
+            generators: vec![],
+            layout: current.layout.clone(),
+        };
+        self.prompt_message("write the schema as one file or as a folder of files?");
+        let is_folder = matches!(current.layout, PrismaLayout::Folder { .. });
+        let layout = Select::new()
+            .items(&["one file", "a folder of files"])
+            .default(usize::from(is_folder))
+            .interact()?;
+        // Table groups are kept when staying on the folder layout, and are edited in the session file.
+        options.layout = match (layout, current.layout) {
+            (0, _) => PrismaLayout::File,
+            (_, PrismaLayout::Folder { groups }) => PrismaLayout::Folder { groups },
+            (_, PrismaLayout::File) => PrismaLayout::Folder { groups: vec![] },
+        };
+        loop {
+            let generator = current
+                .generators
+                .get(options.generators.len())
+                .cloned()
+                .unwrap_or_else(Generator::new);
+            let name = self.prompt_with_default("generator name", &generator.name)?;
+            let provider = self.prompt_with_default("generator provider", &generator.provider)?;
+            let output = self.prompt_with_default(
+                "generator output (empty for the default)",
+                generator.output.as_deref().unwrap_or_default(),
+            )?;
+            options.generators.push(Generator {
+                name,
+                provider,
+                output: Some(output).filter(|output| !output.is_empty()),
+                preview_features: self
+                    .prompt_for_list("preview features", &generator.preview_features)?,
+                binary_targets: self
+                    .prompt_for_list("binary targets", &generator.binary_targets)?,
+            });
+            self.prompt_message("add another generator?");
+            if Select::new().items(&["no", "yes"]).default(0).interact()? == 0 {
+                break;
+            }
+        }
+        self.session.borrow_mut().databases[database_index]
+            .update_prisma_options(AcceptedFormat::Prisma, options);
+        Ok(())
+    }
+
     /// General validator to ensure input isn't empty and hasn't failed
     fn prompt_user_until_successful(&mut self, prompt: &str) -> String {
         let mut input = String::new();
@@ -169,11 +302,18 @@ impl UI for TerminalUI {
 
     fn create_database_entry(&mut self) -> Result<()> {
         self.clear_whole_terminal();
-        let name = self.prompt_user_until_successful("enter the database name");
-        let db_url = self.prompt_user_until_successful("enter the database connection url");
+        let name = self
+            .prompt_user_until_successful("enter the database name")
+            .trim()
+            .to_string();
+        let db_url = self
+            .prompt_user_until_successful("enter the database connection url or .sql file path")
+            .trim()
+            .to_string();
         let disk_mappings = Vec::new();
         let database = database::Database {
             name,
+            source: RemoteSource::from_url(&db_url),
             db_url,
             disk_mappings,
         };
@@ -189,7 +329,7 @@ impl UI for TerminalUI {
     fn select_schema_to_write(&mut self) -> Result<()> {
         self.clear_whole_terminal();
         let db_index = self.select_database()?;
-        let options = AcceptedFormat::all_as_string_array();
+        let options = self.format_options();
         self.prompt_message("which schema would you like to write to the disk?");
         let selection = Select::new()
             .default(0)
@@ -204,11 +344,12 @@ impl UI for TerminalUI {
             .borrow()
             .write_one_schema_from_database(selection, db_index, &options)
         {
-            Ok(_) => {
+            Ok(warnings) => {
+                self.warning_messages(&warnings);
                 self.happy_message("write successful");
             }
             Err(e) => {
-                self.sad_message(format!("error: {}", e.to_string()).as_str());
+                self.sad_message(format!("error: {}", e).as_str());
             }
         }
         Ok(())
@@ -217,7 +358,7 @@ impl UI for TerminalUI {
     fn view_tables_from_disk(&self) -> Result<()> {
         self.clear_terminal_line();
         let db_index = self.select_database()?;
-        let options = AcceptedFormat::all_as_string_array();
+        let options = self.format_options();
         self.prompt_message("which schema would you like to view from the disk?");
         let selection = Select::new()
             .default(0)
@@ -246,7 +387,7 @@ impl UI for TerminalUI {
 
     fn view_tables_from_database(&self) -> Result<()> {
         let db_index = self.select_database()?;
-        let options = AcceptedFormat::all_as_string_array();
+        let options = self.format_options();
         self.prompt_message("which schema would you like view from the database?");
         let selection = Select::new()
             .default(0)
@@ -265,6 +406,43 @@ impl UI for TerminalUI {
         Ok(())
     }
 
+    fn convert_schema(&self) -> Result<()> {
+        let db_index = self.select_database()?;
+        let formats: Vec<AcceptedFormat> = self.session.borrow().databases[db_index]
+            .disk_mappings
+            .iter()
+            .map(|disk_mapping| disk_mapping.format.clone())
+            .collect();
+        if formats.len() < 2 {
+            self.sad_message("add a mapping for each format to convert between first");
+            return Ok(());
+        }
+        let names: Vec<&str> = formats.iter().map(|format| format.as_string()).collect();
+        self.prompt_message("which schema would you like to convert from?");
+        let from = Select::new().default(0).items(&names).interact()?;
+        self.prompt_message("which schema would you like to convert it to?");
+        let to = Select::new()
+            .default(if from == 0 { 1 } else { 0 })
+            .items(&names)
+            .interact()?;
+        if from == to {
+            self.sad_message("pick two different schemas");
+            return Ok(());
+        }
+        match self
+            .session
+            .borrow()
+            .convert(db_index, formats[from].clone(), formats[to].clone())
+        {
+            Ok(warnings) => {
+                self.warning_messages(&warnings);
+                self.happy_message("conversion successful");
+            }
+            Err(e) => self.sad_message(format!("error: {}", e).as_str()),
+        }
+        Ok(())
+    }
+
     fn edit_databases(&mut self) -> Result<()> {
         self.keep_previous_content = false;
         self.clear_terminal_line();
@@ -275,20 +453,52 @@ impl UI for TerminalUI {
 
     fn edit_disk_mappings(&mut self, database_index: usize) -> Result<()> {
         self.clear_whole_terminal();
-        let mut options = AcceptedFormat::all_as_str_array();
+        let mut options = self.session.borrow().registry.formats();
         options.push("exit");
         let selection = Select::new().items(&options).default(0).interact_opt()?;
-        match AcceptedFormat::from_string(
+        let format = self.session.borrow().registry.format(
             options[selection.unwrap_or_else(|| {
                 self.goodbye();
                 exit(0)
             })],
-        ) {
+        );
+        match format {
             Some(x) => {
                 let input = self.prompt_user_until_successful(
                     format!("enter the full path to the {} schema", x.as_string()).as_str(),
                 );
-                self.session.borrow_mut().databases[database_index].update_disk_mapping(x, input);
+                self.session.borrow_mut().databases[database_index]
+                    .update_disk_mapping(x.clone(), input);
+                let orders = TableOrder::all_as_str_array();
+                self.prompt_message("what order should tables be written in?");
+                let order = Select::new().items(&orders).default(0).interact()?;
+                self.session.borrow_mut().databases[database_index]
+                    .update_table_order(x.clone(), TableOrder::all_as_array()[order]);
+                if x == AcceptedFormat::Json {
+                    let shapes = JsonShape::all_as_str_array();
+                    self.prompt_message("how should the json be laid out?");
+                    let shape = Select::new().items(&shapes).default(0).interact()?;
+                    self.session.borrow_mut().databases[database_index]
+                        .update_json_shape(x.clone(), JsonShape::all_as_array()[shape]);
+                }
+                if x == AcceptedFormat::Prisma {
+                    let namings = NamingPolicy::all_as_str_array();
+                    self.prompt_message("how should models and fields be named?");
+                    let naming = Select::new().items(&namings).default(0).interact()?;
+                    self.session.borrow_mut().databases[database_index]
+                        .update_naming(x.clone(), NamingPolicy::all_as_array()[naming]);
+                    self.prompt_message(
+                        "merge into the schema at that path, keeping its hand edits?",
+                    );
+                    let merge = Select::new().items(&["no", "yes"]).default(0).interact()?;
+                    self.session.borrow_mut().databases[database_index]
+                        .update_merge(x.clone(), merge == 1);
+                    self.prompt_message("configure the datasource and generators?");
+                    let configure = Select::new().items(&["no", "yes"]).default(0).interact()?;
+                    if configure == 1 {
+                        self.edit_prisma_options(database_index)?;
+                    }
+                }
             }
             None => {
                 return Ok(());
@@ -298,7 +508,7 @@ impl UI for TerminalUI {
     }
 
     fn edit_database(&mut self, database_index: usize) -> Result<()> {
-        let options = vec!["name", "db_url", "disk_mappings", "done"];
+        let options = vec!["name", "db_url", "source", "disk_mappings", "done"];
         let mut selection = Select::new().items(&options).default(0).interact().unwrap();
         while options[selection] != "done" {
             match options[selection] {
@@ -312,6 +522,13 @@ impl UI for TerminalUI {
                     self.session.borrow_mut().databases[database_index]
                         .update_db_url(input.trim().to_string());
                 }
+                "source" => {
+                    let sources = RemoteSource::all_as_str_array();
+                    self.prompt_message("where should the tables be read from?");
+                    let source = Select::new().items(&sources).default(0).interact()?;
+                    self.session.borrow_mut().databases[database_index]
+                        .update_source(RemoteSource::all_as_array()[source]);
+                }
                 "disk_mappings" => {
                     self.edit_disk_mappings(database_index)?;
                 }
@@ -336,10 +553,48 @@ impl UI for TerminalUI {
     fn display_database(&self, database_index: usize) {
         let database = &self.session.borrow().databases[database_index];
         println!("name: {}", database.name);
+        println!("source: {}", database.source.as_string());
         for mapping in database.disk_mappings.iter() {
+            let format = mapping.format.as_string();
+            println!("{}_path: {}", format, mapping.path);
+            println!(
+                "{}_table_order: {}",
+                format,
+                mapping.table_order.as_string()
+            );
             match mapping.format {
-                AcceptedFormat::Json => println!("json_path: {}", mapping.path),
-                AcceptedFormat::Prisma => println!("prisma_path: {}", mapping.path),
+                AcceptedFormat::Json => {
+                    println!("json_shape: {}", mapping.json_shape.as_string());
+                }
+                AcceptedFormat::Prisma => {
+                    println!("prisma_naming: {}", mapping.naming.as_string());
+                    println!("prisma_merge: {}", mapping.merge);
+                    match &mapping.prisma.layout {
+                        PrismaLayout::File => println!("prisma_layout: file"),
+                        PrismaLayout::Folder { groups } => {
+                            println!("prisma_layout: folder");
+                            for group in groups.iter() {
+                                println!(
+                                    "prisma_group: {} ({})",
+                                    group.name,
+                                    group.tables.join(", ")
+                                );
+                            }
+                        }
+                    }
+                    let datasource = &mapping.prisma.datasource;
+                    println!(
+                        "prisma_datasource: {} (url from {})",
+                        datasource.name, datasource.url_env
+                    );
+                    for generator in mapping.prisma.generators.iter() {
+                        println!(
+                            "prisma_generator: {} ({})",
+                            generator.name, generator.provider
+                        );
+                    }
+                }
+                _ => {}
             }
         }
     }
@@ -363,19 +618,23 @@ impl UI for TerminalUI {
                 0 => {
                     self.display_session();
                 }
-                1 => self.session.borrow_mut().sync()?,
+                1 => {
+                    let warnings = self.session.borrow_mut().sync()?;
+                    self.warning_messages(&warnings);
+                }
                 2 => self.select_schema_to_write()?,
                 3 => self.create_database_entry()?,
                 4 => self.edit_databases()?,
                 5 => self.view_tables_from_disk()?,
                 6 => self.view_tables_from_database()?,
-                7 => {
+                7 => self.convert_schema()?,
+                8 => {
                     self.keep_previous_content = false;
                     self.clear_whole_terminal();
                     self.keep_previous_content = true;
                     self.welcome();
                 }
-                8 => {
+                9 => {
                     self.goodbye();
                     return Ok(());
                 }
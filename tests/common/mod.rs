@@ -0,0 +1,48 @@
+//! Fixtures shared by the integration tests. Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use mysql_translate::remotes::sql::{Description, Dialect, ForeignKey, Key, Table, TableKeys};
+
+pub fn column(field: &str, type_: &str, null: &str, key: &str) -> Description {
+    Description {
+        field: field.to_string(),
+        type_: type_.to_string(),
+        null: null.to_string(),
+        key: key.to_string(),
+        default: None,
+        extra: String::new(),
+        comment: None,
+    }
+}
+
+pub fn foreign_key(name: &str, column: &str, table: &str, referenced: &str) -> Key {
+    Key::Foreign(ForeignKey {
+        constraint_name: name.to_string(),
+        column_names: vec![column.to_string()],
+        referenced_table_name: table.to_string(),
+        referenced_column_names: vec![referenced.to_string()],
+        on_delete: None,
+        on_update: None,
+    })
+}
+
+/// A MySQL table without a comment.
+pub fn table(name: &str, description: Vec<Description>, keys: Vec<Key>) -> Table {
+    Table {
+        name: name.to_string(),
+        dialect: Dialect::MySql,
+        description,
+        keys: TableKeys { keys },
+        comment: None,
+    }
+}
+
+/// A `users` table with only its `id`.
+pub fn users() -> Table {
+    table("users", vec![column("id", "int", "NO", "PRI")], vec![])
+}
+
+/// Collapse the column padding so assertions don't depend on field widths.
+pub fn squash(text: String) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
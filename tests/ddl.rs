@@ -0,0 +1,118 @@
+use mysql_translate::remotes::{
+    ddl::parse_tables,
+    sql::{Key, ReferentialAction},
+};
+
+static DUMP: &str = "
+-- MySQL dump 10.13  Distrib 8.0.33, for macos13 (arm64)
+/*!40101 SET @OLD_CHARACTER_SET_CLIENT=@@CHARACTER_SET_CLIENT */;
+DROP TABLE IF EXISTS `users`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+CREATE TABLE `users` (
+  `id` int unsigned NOT NULL AUTO_INCREMENT,
+  `email` varchar(255) COLLATE utf8mb4_unicode_ci NOT NULL,
+  `role` enum('admin','it''s') NOT NULL DEFAULT 'admin',
+  `balance` decimal(10,2) DEFAULT '0.00',
+  `created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `updated_at` datetime(3) DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `users_email_key` (`email`)
+) ENGINE=InnoDB AUTO_INCREMENT=4 DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='People who can sign in';
+
+CREATE TABLE `memberships` (
+  `team_id` int NOT NULL,
+  `user_id` int unsigned NOT NULL,
+  `note` text COMMENT 'Why they joined, in their own words',
+  PRIMARY KEY (`team_id`,`user_id`),
+  KEY `memberships_user_id_fk` (`user_id`),
+  CONSTRAINT `memberships_user_id_fk` FOREIGN KEY (`user_id`) REFERENCES `users` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+";
+
+#[test]
+pub fn ddl_columns() {
+    let tables = parse_tables(DUMP).expect("dump to parse");
+    assert_eq!(tables.len(), 2);
+    let users = &tables[1];
+    assert_eq!(users.name, "users");
+    let id = &users.description[0];
+    assert_eq!(
+        (id.type_.as_str(), id.null.as_str(), id.key.as_str()),
+        ("int unsigned", "NO", "PRI")
+    );
+    assert_eq!(id.extra, "auto_increment");
+    assert_eq!(users.description[1].key, "UNI");
+    assert_eq!(users.description[2].type_, "enum('admin','it''s')");
+    assert_eq!(users.description[2].default.as_deref(), Some("admin"));
+    assert_eq!(users.description[3].null, "YES");
+    assert_eq!(users.description[3].default.as_deref(), Some("0.00"));
+    assert_eq!(users.description[4].extra, "DEFAULT_GENERATED");
+    assert_eq!(
+        users.description[5].extra,
+        "DEFAULT_GENERATED on update CURRENT_TIMESTAMP(3)"
+    );
+    assert_eq!(users.comment.as_deref(), Some("People who can sign in"));
+    assert_eq!(users.description[0].comment, None);
+    let memberships = &tables[0];
+    assert_eq!(memberships.comment, None);
+    assert_eq!(
+        memberships.description[2].comment.as_deref(),
+        Some("Why they joined, in their own words")
+    );
+}
+
+#[test]
+pub fn ddl_keys() {
+    let tables = parse_tables(DUMP).expect("dump to parse");
+    let memberships = &tables[0];
+    assert_eq!(memberships.description[0].key, "PRI");
+    assert_eq!(memberships.description[1].key, "PRI");
+    let foreign_key = memberships
+        .keys
+        .keys
+        .iter()
+        .find_map(|key| match key {
+            Key::Foreign(foreign_key) => Some(foreign_key),
+            _ => None,
+        })
+        .expect("foreign key to be parsed");
+    assert_eq!(foreign_key.constraint_name, "memberships_user_id_fk");
+    assert_eq!(foreign_key.column_names, vec!["user_id"]);
+    assert_eq!(foreign_key.referenced_table_name, "users");
+    assert_eq!(foreign_key.on_delete, Some(ReferentialAction::Cascade));
+    assert_eq!(foreign_key.on_update, Some(ReferentialAction::NoAction));
+    assert!(memberships.keys.keys.iter().any(|key| matches!(
+        key,
+        Key::Index(index_key) if index_key.constraint_name == "memberships_user_id_fk"
+    )));
+    let users = &tables[1];
+    assert!(users.keys.keys.iter().any(|key| matches!(
+        key,
+        Key::Unique(unique_keys) if unique_keys[0].constraint_name == "users_email_key"
+    )));
+}
+
+#[test]
+pub fn ddl_versioned_comments() {
+    let dump = "
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+CREATE TABLE `events` (
+  `id` int NOT NULL,
+  `audit` varchar(64) DEFAULT NULL /*!80023 INVISIBLE */,
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB COMMENT='Things that happened'
+/*!50100 PARTITION BY RANGE (`id`)
+(PARTITION p0 VALUES LESS THAN (1000) COMMENT = 'old' ENGINE = InnoDB) */;
+/*!50001 CREATE TABLE `recent_events` (
+  `id` tinyint NOT NULL
+) ENGINE=MyISAM */;
+/*!50001 DROP TABLE IF EXISTS `recent_events`*/;
+/*!50001 CREATE ALGORITHM=UNDEFINED */
+/*!50001 VIEW `recent_events` AS select `events`.`id` AS `id` from `events` */;
+";
+    let tables = parse_tables(dump).expect("dump to parse");
+    assert_eq!(tables.len(), 1);
+    let events = &tables[0];
+    assert_eq!(events.description[1].extra, "INVISIBLE");
+    assert_eq!(events.comment.as_deref(), Some("Things that happened"));
+}
@@ -25,9 +25,16 @@ pub fn prisma_db_write() {
         path: prisma_disk_mapping.path.to_owned(),
         disk_schema: None,
         db_schema: None,
+        naming: prisma_disk_mapping.naming,
+        merge: prisma_disk_mapping.merge,
+        options: prisma_disk_mapping.prisma.clone(),
     };
-    let descriptions = session.databases[0].get_descriptions();
-    translator.write_to_disk(&descriptions)
+    let descriptions = session.databases[0]
+        .get_descriptions()
+        .expect("database to be read");
+    translator
+        .write_to_disk(&descriptions)
+        .expect("write to succeed");
 }
 
 #[test]
@@ -45,10 +52,13 @@ pub fn json_db_pull() {
     let mut translator = JsonTranslator {
         path: prisma_disk_mapping.path.to_owned(),
         json: None,
+        shape: prisma_disk_mapping.json_shape,
     };
-    let descriptions = session.databases[0].get_descriptions();
+    let descriptions = session.databases[0]
+        .get_descriptions()
+        .expect("database to be read");
     translator.load_from_database(&descriptions);
-    assert!(!translator.json.is_none());
+    assert!(translator.json.is_some());
 }
 
 fn main() {
@@ -0,0 +1,260 @@
+mod common;
+
+use common::{column, table};
+use mysql_translate::{
+    remotes::{
+        ddl::parse_tables,
+        sql::{Description, ForeignKey, Key, ReferentialAction, Table},
+    },
+    translators::{
+        behaviour::TranslatorBehaviour,
+        json::{
+            document::{
+                JsonColumn, JsonConstraint, JsonDocument, JsonForeignKey, DOCUMENT_VERSION,
+            },
+            shape::JsonShape,
+        },
+        json_translator::JsonTranslator,
+        ordering::TableOrder,
+    },
+};
+
+#[test]
+pub fn json_foreign_keys() {
+    let posts = Table {
+        comment: Some("Published writing".to_string()),
+        ..table(
+            "posts",
+            vec![
+                column("id", "int", "NO", "PRI"),
+                Description {
+                    comment: Some("Who wrote the post".to_string()),
+                    ..column("author_id", "int", "NO", "MUL")
+                },
+            ],
+            vec![Key::Foreign(ForeignKey {
+                constraint_name: "fk_author".to_string(),
+                column_names: vec!["author_id".to_string()],
+                referenced_table_name: "users".to_string(),
+                referenced_column_names: vec!["id".to_string()],
+                on_delete: Some(ReferentialAction::Cascade),
+                on_update: Some(ReferentialAction::NoAction),
+            })],
+        )
+    };
+    let translator = JsonTranslator {
+        path: String::new(),
+        json: None,
+        shape: JsonShape::Legacy,
+    };
+    let json = translator.get_translation(&[posts]);
+    assert_eq!(
+        json["tables"]["posts"]["author_id"],
+        "int MUL NOT NULL REFERENCES users(id) ON DELETE CASCADE ON UPDATE NO ACTION COMMENT 'Who wrote the post'"
+    );
+    assert_eq!(json["tables"]["posts"]["id"], "int PRI NOT NULL");
+    assert_eq!(json["descriptions"]["posts"], "Published writing");
+}
+
+#[test]
+pub fn json_structured() {
+    let tables = parse_tables(
+        "
+CREATE TABLE `posts` (
+  `id` int NOT NULL AUTO_INCREMENT,
+  `author_id` int NOT NULL COMMENT 'Who wrote it',
+  `slug` varchar(191) NOT NULL,
+  `status` varchar(20) NOT NULL DEFAULT 'draft',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `posts_slug_key` (`slug`),
+  KEY `posts_author_status_idx` (`author_id`,`status`),
+  CONSTRAINT `posts_author_fk` FOREIGN KEY (`author_id`) REFERENCES `users` (`id`) ON DELETE CASCADE
+) COMMENT='Published writing';
+",
+    )
+    .expect("dump to parse");
+    let translator = JsonTranslator {
+        path: String::new(),
+        json: None,
+        shape: JsonShape::Structured,
+    };
+    let json = translator.get_translation(&tables);
+    assert_eq!(json["version"], DOCUMENT_VERSION);
+    assert_eq!(json["tables"][0]["columns"][0]["type"], "int");
+
+    let document: JsonDocument = serde_json::from_value(json).expect("document to read back");
+    let posts = &document.tables[0];
+    assert_eq!(posts.name, "posts");
+    assert_eq!(posts.dialect, "mysql");
+    assert_eq!(posts.comment.as_deref(), Some("Published writing"));
+    assert_eq!(
+        posts.columns[0],
+        JsonColumn {
+            name: "id".to_string(),
+            type_: "int".to_string(),
+            nullable: false,
+            default: None,
+            extra: Some("auto_increment".to_string()),
+            comment: None,
+        }
+    );
+    assert_eq!(posts.columns[1].comment.as_deref(), Some("Who wrote it"));
+    assert_eq!(posts.columns[3].default.as_deref(), Some("draft"));
+    assert_eq!(posts.primary_key, vec!["id"]);
+    assert_eq!(
+        posts.unique_constraints,
+        vec![JsonConstraint {
+            name: "posts_slug_key".to_string(),
+            columns: vec!["slug".to_string()],
+        }]
+    );
+    assert!(posts.indexes.contains(&JsonConstraint {
+        name: "posts_author_status_idx".to_string(),
+        columns: vec!["author_id".to_string(), "status".to_string()],
+    }));
+    assert_eq!(
+        posts.foreign_keys,
+        vec![JsonForeignKey {
+            name: "posts_author_fk".to_string(),
+            columns: vec!["author_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: Some("CASCADE".to_string()),
+            on_update: Some("NO ACTION".to_string()),
+        }]
+    );
+}
+
+#[test]
+pub fn json_stable_order() {
+    let dump = "
+CREATE TABLE `users` (`id` int NOT NULL, `name` text, `email` text, PRIMARY KEY (`id`));
+CREATE TABLE `tags` (`id` int NOT NULL, PRIMARY KEY (`id`));
+CREATE TABLE `posts` (
+  `id` int NOT NULL,
+  `author_id` int NOT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `posts_z_key` (`id`,`author_id`),
+  UNIQUE KEY `posts_a_key` (`author_id`,`id`),
+  CONSTRAINT `posts_author_fk` FOREIGN KEY (`author_id`) REFERENCES `users` (`id`)
+);
+CREATE TABLE `comments` (
+  `id` int NOT NULL,
+  `post_id` int NOT NULL,
+  CONSTRAINT `comments_post_fk` FOREIGN KEY (`post_id`) REFERENCES `posts` (`id`)
+);
+";
+    let tables = parse_tables(dump).expect("dump to parse");
+    let mut reversed = parse_tables(dump).expect("dump to parse");
+    reversed.reverse();
+    let names =
+        |tables: &[Table]| -> Vec<String> { tables.iter().map(|t| t.name.clone()).collect() };
+    assert_eq!(
+        names(&TableOrder::Alphabetical.ordered(&reversed)),
+        ["comments", "posts", "tags", "users"]
+    );
+    assert_eq!(
+        names(&TableOrder::Dependency.ordered(&reversed)),
+        ["tags", "users", "posts", "comments"]
+    );
+
+    for shape in JsonShape::all_as_array() {
+        let translator = JsonTranslator {
+            path: String::new(),
+            json: None,
+            shape,
+        };
+        let text = |tables: &Vec<Table>| {
+            let ordered = TableOrder::Alphabetical.ordered(tables);
+            serde_json::to_string_pretty(&translator.get_translation(&ordered)).unwrap()
+        };
+        assert_eq!(text(&tables), text(&reversed));
+    }
+
+    let translator = JsonTranslator {
+        path: String::new(),
+        json: None,
+        shape: JsonShape::Legacy,
+    };
+    let json = translator.get_translation(&tables);
+    let columns: Vec<&String> = json["tables"]["users"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .collect();
+    assert_eq!(columns, ["id", "name", "email"]);
+
+    let document: JsonDocument = serde_json::from_value(
+        JsonTranslator {
+            shape: JsonShape::Structured,
+            ..translator
+        }
+        .get_translation(&tables),
+    )
+    .unwrap();
+    let posts = document.tables.iter().find(|t| t.name == "posts").unwrap();
+    let unique_names: Vec<&str> = posts
+        .unique_constraints
+        .iter()
+        .map(|constraint| constraint.name.as_str())
+        .collect();
+    assert_eq!(unique_names, ["posts_a_key", "posts_z_key"]);
+}
+
+#[test]
+pub fn json_to_tables() {
+    let tables = parse_tables(
+        "
+CREATE TABLE `users` (
+  `id` int NOT NULL AUTO_INCREMENT,
+  `email` varchar(255) NOT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `users_email_key` (`email`)
+) COMMENT='People who can sign in';
+CREATE TABLE `posts` (
+  `id` int NOT NULL AUTO_INCREMENT,
+  `author_id` int DEFAULT NULL,
+  `status` enum('draft','published') NOT NULL DEFAULT 'draft',
+  `created_at` datetime(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+  PRIMARY KEY (`id`),
+  KEY `posts_author_status_idx` (`author_id`,`status`),
+  CONSTRAINT `posts_author_fk` FOREIGN KEY (`author_id`) REFERENCES `users` (`id`) ON DELETE SET NULL
+);
+",
+    )
+    .expect("dump to parse");
+    let document = JsonDocument::from(tables.as_slice());
+    let read_back = document.to_tables().expect("document to read back");
+    assert_eq!(JsonDocument::from(read_back.as_slice()), document);
+    let posts = read_back
+        .iter()
+        .find(|table| table.name == "posts")
+        .unwrap();
+    let keys: Vec<&str> = posts
+        .description
+        .iter()
+        .map(|description| description.key.as_str())
+        .collect();
+    assert_eq!(keys, ["PRI", "MUL", "", ""]);
+
+    let mut translator = JsonTranslator {
+        path: String::new(),
+        json: Some(serde_json::to_value(&document).unwrap()),
+        shape: JsonShape::Structured,
+    };
+    assert_eq!(translator.to_tables().unwrap().len(), 2);
+    translator.json = Some(
+        JsonTranslator {
+            path: String::new(),
+            json: None,
+            shape: JsonShape::Legacy,
+        }
+        .get_translation(&tables),
+    );
+    assert!(translator.to_tables().is_err());
+    let newer = JsonDocument {
+        version: DOCUMENT_VERSION + 1,
+        tables: vec![],
+    };
+    assert!(newer.to_tables().is_err());
+}
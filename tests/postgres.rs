@@ -0,0 +1,92 @@
+use mysql_translate::remotes::{
+    postgres::get_table_descriptions,
+    sql::{Dialect, Key, ReferentialAction},
+};
+use mysql_translate::translators::prisma::schema::PrismaSchema;
+use postgres::{Client, NoTls};
+
+static SCHEMA: &str = "
+DROP TABLE IF EXISTS pg_memberships;
+DROP TABLE IF EXISTS pg_users;
+CREATE TABLE pg_users (
+    id serial PRIMARY KEY,
+    email character varying(255) NOT NULL,
+    status text NOT NULL DEFAULT 'draft',
+    balance numeric(10,2),
+    tags text[],
+    created_at timestamp(3) NOT NULL DEFAULT now(),
+    CONSTRAINT pg_users_email_key UNIQUE (email)
+);
+CREATE TABLE pg_memberships (
+    team_id integer NOT NULL,
+    user_id integer NOT NULL REFERENCES pg_users (id) ON DELETE CASCADE,
+    note text,
+    PRIMARY KEY (team_id, user_id)
+);
+CREATE INDEX pg_memberships_note_idx ON pg_memberships (note, user_id);
+COMMENT ON TABLE pg_users IS 'People who can sign in';
+COMMENT ON COLUMN pg_users.email IS 'Where receipts are sent';
+";
+
+/// Needs a scratch Postgres database, for example
+/// `POSTGRES_TEST_URL=postgresql://postgres@localhost/postgres cargo test -- --ignored`.
+#[test]
+#[ignore]
+fn postgres_introspection() {
+    let url = std::env::var("POSTGRES_TEST_URL").expect("POSTGRES_TEST_URL to be set");
+    let mut client = Client::connect(&url, NoTls).expect("to connect");
+    client.batch_execute(SCHEMA).expect("schema to apply");
+
+    let tables: Vec<_> = get_table_descriptions(&url)
+        .expect("introspection to succeed")
+        .into_iter()
+        .filter(|t| t.name.starts_with("pg_"))
+        .collect();
+    let users = tables.iter().find(|t| t.name == "pg_users").unwrap();
+    let memberships = tables.iter().find(|t| t.name == "pg_memberships").unwrap();
+    assert_eq!(users.dialect, Dialect::Postgres);
+
+    let id = &users.description[0];
+    assert_eq!(id.type_, "integer");
+    assert_eq!(id.key, "PRI");
+    assert_eq!(id.extra, "auto_increment");
+    assert_eq!(id.default, None);
+    assert_eq!(users.description[1].key, "UNI");
+    assert_eq!(users.description[2].default.as_deref(), Some("draft"));
+    assert_eq!(users.description[3].null, "YES");
+    assert_eq!(
+        users.description[5].default.as_deref(),
+        Some("CURRENT_TIMESTAMP")
+    );
+    assert_eq!(users.comment.as_deref(), Some("People who can sign in"));
+    assert_eq!(
+        users.description[1].comment.as_deref(),
+        Some("Where receipts are sent")
+    );
+    assert_eq!(memberships.comment, None);
+
+    let foreign_key = memberships
+        .keys
+        .keys
+        .iter()
+        .find_map(|key| match key {
+            Key::Foreign(foreign_key) => Some(foreign_key),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(foreign_key.column_names, vec!["user_id"]);
+    assert_eq!(foreign_key.referenced_table_name, "pg_users");
+    assert_eq!(foreign_key.on_delete, Some(ReferentialAction::Cascade));
+    assert!(memberships.keys.keys.iter().any(|key| matches!(
+        key,
+        Key::MultiIndex(index_keys) if index_keys[0].constraint_name == "pg_memberships_note_idx"
+    )));
+
+    let schema = PrismaSchema::from(&tables).as_text();
+    let squashed: String = schema.split_whitespace().collect::<Vec<&str>>().join(" ");
+    assert!(squashed.contains("provider = \"postgresql\""));
+    assert!(squashed.contains("email String @unique(map: \"pg_users_email_key\") @db.VarChar(255)"));
+    assert!(squashed.contains("balance Decimal? @db.Decimal(10, 2)"));
+    assert!(squashed.contains("tags String[]"));
+    assert!(squashed.contains("created_at DateTime @default(now()) @db.Timestamp(3)"));
+}
@@ -0,0 +1,1100 @@
+mod common;
+
+use common::{column, foreign_key, squash, table, users};
+use mysql_translate::{
+    functionality::{
+        database::Database,
+        session::Session,
+        structure::{AcceptedFormat, DiskMapping, RemoteSource},
+    },
+    remotes::sql::{
+        Description, Dialect, ForeignKey, IndexKey, Key, ReferentialAction, Table, UniqueKey,
+    },
+    translators::{
+        behaviour::TranslatorBehaviour,
+        prisma::{
+            diff::{AttributeChange, SchemaDiff},
+            generator::Generator,
+            merge::merge,
+            naming::NamingPolicy,
+            options::{DatasourceOptions, ModelGroup, PrismaLayout, PrismaOptions},
+            parser::{BlockKind, Expression, Position, SchemaFile},
+            schema::PrismaSchema,
+        },
+        prisma_translator::{PrismaTranslator, OUTPUT_FOLDER},
+        registry::TranslatorRegistry,
+    },
+};
+
+#[test]
+pub fn prisma_relation_fields() {
+    let posts = table(
+        "posts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("author_id", "int", "NO", "MUL"),
+        ],
+        vec![foreign_key("fk_author", "author_id", "users", "id")],
+    );
+    let text = squash(PrismaSchema::from(&vec![users(), posts]).as_text());
+    assert!(text.contains("users users @relation(fields: [author_id], references: [id])"));
+    assert!(text.contains("posts posts[]"));
+}
+
+#[test]
+pub fn prisma_ambiguous_relation_names() {
+    let posts = table(
+        "posts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("author_id", "int", "NO", "MUL"),
+            column("editor_id", "int", "YES", "MUL"),
+        ],
+        vec![
+            foreign_key("fk_author", "author_id", "users", "id"),
+            foreign_key("fk_editor", "editor_id", "users", "id"),
+        ],
+    );
+    let text = squash(PrismaSchema::from(&vec![users(), posts]).as_text());
+    assert!(text
+        .contains("@relation(\"posts_author_idTousers\", fields: [author_id], references: [id])"));
+    assert!(text.contains("users_posts_editor_idTousers users?"));
+    assert!(text.contains("posts_posts_editor_idTousers posts[]"));
+    assert!(text.contains("@relation(\"posts_editor_idTousers\")"));
+}
+
+#[test]
+pub fn prisma_index_directives() {
+    let memberships = table(
+        "memberships",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("code", "varchar(16)", "NO", "UNI"),
+            column("team_id", "int", "NO", "MUL"),
+            column("user_id", "int", "NO", "MUL"),
+            column("role", "varchar(16)", "NO", ""),
+        ],
+        vec![
+            Key::Unique(vec![UniqueKey {
+                constraint_name: "code_unique".to_string(),
+                column_names: vec!["code".to_string()],
+            }]),
+            Key::Unique(vec![UniqueKey {
+                constraint_name: "team_user_unique".to_string(),
+                column_names: vec!["team_id".to_string(), "user_id".to_string()],
+            }]),
+            Key::Index(IndexKey {
+                constraint_name: "user_idx".to_string(),
+                column_name: "user_id".to_string(),
+            }),
+            Key::MultiIndex(vec![
+                IndexKey {
+                    constraint_name: "team_role_idx".to_string(),
+                    column_name: "team_id".to_string(),
+                },
+                IndexKey {
+                    constraint_name: "team_role_idx".to_string(),
+                    column_name: "role".to_string(),
+                },
+            ]),
+        ],
+    );
+    let text = squash(PrismaSchema::from(&vec![memberships]).as_text());
+    assert!(text.contains("@unique(map: \"code_unique\")"));
+    assert!(!text.contains("@id @unique"));
+    assert!(text.contains("@@unique([team_id, user_id], map: \"team_user_unique\")"));
+    assert!(text.contains("@@index([user_id], map: \"user_idx\")"));
+    assert!(text.contains("@@index([team_id, role], map: \"team_role_idx\")"));
+}
+
+#[test]
+pub fn prisma_referential_actions() {
+    let posts = table(
+        "posts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("author_id", "int", "YES", "MUL"),
+        ],
+        vec![Key::Foreign(ForeignKey {
+            constraint_name: "fk_author".to_string(),
+            column_names: vec!["author_id".to_string()],
+            referenced_table_name: "users".to_string(),
+            referenced_column_names: vec!["id".to_string()],
+            on_delete: Some(ReferentialAction::SetNull),
+            on_update: Some(ReferentialAction::Cascade),
+        })],
+    );
+    let text = squash(PrismaSchema::from(&vec![users(), posts]).as_text());
+    assert!(text.contains(
+        "@relation(fields: [author_id], references: [id], onDelete: SetNull, onUpdate: Cascade)"
+    ));
+}
+
+#[test]
+pub fn prisma_composite_relation() {
+    let teams = table(
+        "team_members",
+        vec![
+            column("team_id", "int", "NO", "PRI"),
+            column("user_id", "int", "NO", "PRI"),
+        ],
+        vec![],
+    );
+    let assignments = table(
+        "assignments",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("team_id", "int", "NO", "MUL"),
+            column("member_id", "int", "NO", ""),
+        ],
+        vec![Key::Foreign(ForeignKey {
+            constraint_name: "fk_member".to_string(),
+            column_names: vec!["team_id".to_string(), "member_id".to_string()],
+            referenced_table_name: "team_members".to_string(),
+            referenced_column_names: vec!["team_id".to_string(), "user_id".to_string()],
+            on_delete: None,
+            on_update: None,
+        })],
+    );
+    let text = squash(PrismaSchema::from(&vec![teams, assignments]).as_text());
+    assert!(text.contains(
+        "team_members team_members @relation(fields: [team_id, member_id], references: [team_id, user_id])"
+    ));
+    assert!(text.contains("assignments assignments[]"));
+}
+
+#[test]
+pub fn prisma_column_types() {
+    let table = table(
+        "samples",
+        vec![
+            column("id", "bigint unsigned", "NO", "PRI"),
+            column("views", "bigint", "YES", ""),
+            column("is_active", "tinyint(1)", "NO", ""),
+            column("rank", "tinyint", "NO", ""),
+            column("price", "decimal(10,2)", "NO", ""),
+            column("code", "char(3)", "NO", ""),
+            column("hash", "binary(16)", "NO", ""),
+            column("body", "longtext", "YES", ""),
+            column("location", "point", "YES", ""),
+            column("area", "GEOMETRY", "NO", ""),
+            column("flags", "bit(8)", "NO", ""),
+            column("enabled", "bit(1)", "NO", ""),
+            column("summary", "TINYTEXT", "NO", ""),
+            column("token", "varbinary(32)", "NO", ""),
+            column("thumbnail", "blob", "NO", ""),
+            column("original", "longblob", "NO", ""),
+            column("tags", "set('a','b')", "NO", ""),
+            column("embedding", "vector(3)", "NO", ""),
+        ],
+        vec![],
+    );
+    let text = squash(PrismaSchema::from(&vec![table]).as_text());
+    assert!(text.contains("id BigInt @id @db.UnsignedBigInt"));
+    assert!(text.contains("views BigInt?"));
+    assert!(text.contains("is_active Boolean"));
+    assert!(text.contains("rank Int @db.TinyInt"));
+    assert!(text.contains("price Decimal @db.Decimal(10, 2)"));
+    assert!(text.contains("code String @db.Char(3)"));
+    assert!(text.contains("hash Bytes @db.Binary(16)"));
+    assert!(text.contains("body String? @db.LongText"));
+    assert!(text.contains("location Unsupported(\"point\")?"));
+    assert!(text.contains("area Unsupported(\"geometry\")"));
+    assert!(text.contains("flags Bytes @db.Bit(8)"));
+    assert!(text.contains("enabled Boolean @db.Bit(1)"));
+    assert!(text.contains("summary String @db.TinyText"));
+    assert!(text.contains("token Bytes @db.VarBinary(32)"));
+    assert!(text.contains("thumbnail Bytes @db.Blob"));
+    assert!(text.contains("original Bytes tags String embedding"));
+    assert!(text.contains("embedding Unsupported(\"vector(3)\")"));
+}
+
+#[test]
+pub fn prisma_enums() {
+    let mut status = column("status", "enum('draft','in review','2fa')", "NO", "");
+    status.default = Some("in review".to_string());
+    let posts = table(
+        "posts",
+        vec![column("id", "int", "NO", "PRI"), status],
+        vec![],
+    );
+    let schema = PrismaSchema::from(&vec![posts]);
+    let text = squash(schema.as_text());
+    assert!(text.contains("status posts_status @default(in_review)"));
+    assert!(text
+        .contains("enum posts_status { draft in_review @map(\"in review\") _2fa @map(\"2fa\") }"));
+
+    let path = std::env::temp_dir().join(format!("prisma_enums_{}.prisma", std::process::id()));
+    std::fs::write(&path, schema.as_text()).expect("schema to be written");
+    let translator = PrismaTranslator {
+        path: path.display().to_string(),
+        disk_schema: None,
+        db_schema: None,
+        naming: NamingPolicy::Database,
+        merge: false,
+        options: PrismaOptions::default(),
+    };
+    let from_disk = translator.parse_from_disk().expect("schema to parse");
+    std::fs::remove_file(&path).expect("schema to be removed");
+    assert!(from_disk == schema);
+}
+
+#[test]
+pub fn prisma_naming_policy() {
+    let user_accounts = table(
+        "user_accounts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("created_at", "datetime(3)", "NO", ""),
+        ],
+        vec![],
+    );
+    let blog_posts = table(
+        "blog_posts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("author_id", "int", "NO", "MUL"),
+        ],
+        vec![
+            foreign_key("fk_author", "author_id", "user_accounts", "id"),
+            Key::Index(IndexKey {
+                constraint_name: "fk_author".to_string(),
+                column_name: "author_id".to_string(),
+            }),
+        ],
+    );
+    let tables = vec![user_accounts, blog_posts];
+    let text = squash(PrismaSchema::from_tables(&tables, NamingPolicy::Conventional).as_text());
+    assert!(text.contains("model UserAccount { id Int @id createdAt DateTime @map(\"created_at\")"));
+    assert!(text.contains("blogPosts BlogPost[] @@map(\"user_accounts\") }"));
+    assert!(
+        text.contains("userAccount UserAccount @relation(fields: [authorId], references: [id])")
+    );
+    assert!(text.contains("@@index([authorId], map: \"fk_author\") @@map(\"blog_posts\")"));
+
+    let text = squash(PrismaSchema::from(&tables).as_text());
+    assert!(text.contains("model user_accounts {"));
+    assert!(!text.contains("@map"));
+}
+
+#[test]
+pub fn prisma_merge() {
+    let posts = table(
+        "posts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("author_id", "int", "NO", "MUL"),
+            column("title", "varchar(191)", "NO", ""),
+        ],
+        vec![foreign_key("fk_author", "author_id", "users", "id")],
+    );
+    let mut users = users();
+    users
+        .description
+        .push(column("updated_at", "datetime(3)", "NO", ""));
+    let tags = table("tags", vec![column("id", "int", "NO", "PRI")], vec![]);
+    let tables = vec![users, posts, tags];
+    let disk_text = r#"generator client {
+  provider        = "prisma-client-js"
+  previewFeatures = ["fullTextSearch"]
+}
+
+datasource db {
+  provider = "mysql"
+  url      = env("DATABASE_URL")
+}
+
+/// Everyone who can sign in.
+model users {
+  id         Int      @id
+  posts      posts[]  @relation("written")
+  updated_at DateTime @updatedAt @db.DateTime(3) // kept by hand
+}
+
+model posts {
+  id        Int    @id
+  author_id Int
+  legacy    String
+  author    users  @relation("written", fields: [author_id], references: [id])
+}
+
+model sessions {
+  id Int @id
+}
+"#;
+    let path = std::env::temp_dir().join(format!("prisma_merge_{}.prisma", std::process::id()));
+    std::fs::write(&path, disk_text).expect("schema to be written");
+    let translator = PrismaTranslator {
+        path: path.display().to_string(),
+        disk_schema: None,
+        db_schema: None,
+        naming: NamingPolicy::Database,
+        merge: true,
+        options: PrismaOptions::default(),
+    };
+    translator.merge_to_disk(&tables).expect("schema to merge");
+    let merged = std::fs::read_to_string(&path).expect("schema to be read");
+    std::fs::remove_file(&path).expect("schema to be removed");
+    let text = squash(merged);
+    assert!(text.contains("previewFeatures = [\"fullTextSearch\"]"));
+    assert!(text.contains("/// Everyone who can sign in. model users {"));
+    assert!(text.contains("updated_at DateTime @updatedAt // kept by hand"));
+    assert!(
+        text.contains("author users @relation(\"written\", fields: [author_id], references: [id])")
+    );
+    assert!(text.contains("author_id Int title String author users"));
+    assert!(!text.contains("legacy"));
+    assert!(!text.contains("sessions"));
+    assert!(text.ends_with("} model tags { id Int @id }"));
+    assert!(text.contains("posts posts[] @relation(\"written\")"));
+    assert_eq!(text.matches("@relation").count(), 2);
+}
+
+#[test]
+pub fn prisma_merge_layout() {
+    let mut users = users();
+    users
+        .description
+        .push(column("name", "varchar(191)", "NO", ""));
+    let posts = table(
+        "posts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("author_id", "int", "NO", "MUL"),
+            column("editor_id", "int", "NO", "MUL"),
+        ],
+        vec![foreign_key("fk_author", "author_id", "users", "id")],
+    );
+    let mut tags = users.clone();
+    tags.name = "tags".to_string();
+    let disk_text = r#"// model comments { not a block }
+model users {
+  id    Int     @id
+  name  String  @default("{ }") // }
+  posts posts[] @relation("written")
+  edits posts[] @relation("edited")
+
+  @@fulltext([
+    name
+  ])
+}
+
+model posts {
+  id        Int   @id
+  author_id Int
+  editor_id Int
+  writer    users @relation("written", fields: [author_id], references: [id])
+  editor    users @relation("edited", fields: [editor_id], references: [id])
+
+  @@index([
+    author_id
+  ], map: "fk_author")
+}
+
+model sessions {}
+
+model tags { id Int @id }
+"#;
+    let merged =
+        merge(disk_text, &PrismaSchema::from(&vec![users, posts, tags])).expect("schema to merge");
+    assert!(merged.starts_with(
+        "// model comments { not a block }
+model users {
+"
+    ));
+    assert!(merged.contains("  name  String  @default(\"{ }\") // }\n"));
+    assert!(merged.contains("  @@fulltext([\n    name\n  ])\n}"));
+    let text = squash(merged);
+    // The foreign key behind editor is gone, so both ends of its relation are too.
+    assert!(text.contains("posts posts[] @relation(\"written\")"));
+    assert!(!text.contains("edits"));
+    assert!(
+        text.contains("writer users @relation(\"written\", fields: [author_id], references: [id])")
+    );
+    assert!(!text.contains("editor users"));
+    assert!(text.contains("editor_id Int writer users"));
+    assert!(!text.contains("@@index"));
+    assert!(!text.contains("sessions"));
+    assert!(text.ends_with("} model tags { id Int @id name String }"));
+}
+
+#[test]
+pub fn prisma_parser() {
+    let text = r#"// schema.prisma
+generator client {
+  provider = "prisma-client-js"
+}
+
+/// A person.
+/// Maybe two.
+model users {
+  id    Int     @id @default(autoincrement())
+  name  String  @default("say \"hi\"") // quoted
+  posts posts[]
+
+  @@index([
+    name,
+    id
+  ], map: "users_name")
+}
+
+view active_users {
+  id Int @unique
+}
+
+type Address {
+  street String
+}
+
+enum role {
+  admin
+  two_factor @map("2fa")
+}
+
+model posts {
+  id       Int                      @id
+  author   users                    @relation(fields: [author_id], references: [id], onDelete: Cascade)
+  author_id Int
+  location Unsupported("point")?
+}
+"#;
+    let file = SchemaFile::parse(text).expect("schema to parse");
+    assert_eq!(file.as_text(), text);
+    let kinds: Vec<BlockKind> = file.blocks.iter().map(|block| block.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            BlockKind::Generator,
+            BlockKind::Model,
+            BlockKind::View,
+            BlockKind::Type,
+            BlockKind::Enum,
+            BlockKind::Model,
+        ]
+    );
+    let model = &file.blocks[1];
+    assert_eq!(model.documentation, vec!["A person.", "Maybe two."]);
+    assert!(file.block_text(model).starts_with("/// A person."));
+    let name = &model.fields[1];
+    assert_eq!(name.comment.as_deref(), Some("// quoted"));
+    assert_eq!(
+        name.attribute("default").unwrap().argument(None),
+        Some(&Expression::String("say \"hi\"".to_string()))
+    );
+    assert_eq!(
+        model.attributes[0].as_text(),
+        "@@index([name, id], map: \"users_name\")"
+    );
+    assert_eq!(
+        model.attributes[0].position,
+        Position {
+            line: 13,
+            column: 3
+        }
+    );
+    assert_eq!(
+        file.blocks[5].fields[3].field_type.unsupported.as_deref(),
+        Some("point")
+    );
+
+    let error = SchemaFile::parse("model users {\n  id Int @id(\n}\n").unwrap_err();
+    assert_eq!(error.position, Position { line: 3, column: 1 });
+    assert_eq!(
+        error.to_string(),
+        "line 3, column 1: expected a value, found `}`"
+    );
+    let error = SchemaFile::parse("modle users {\n}\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 1, column 1: unknown block type `modle`"
+    );
+    let error = SchemaFile::parse("model users {\n  name String @default(\"a)\n}").unwrap_err();
+    assert_eq!(
+        error.position,
+        Position {
+            line: 2,
+            column: 24
+        }
+    );
+    // A model without fields is valid, and reads back without a panic.
+    let file = SchemaFile::parse("model draft {}\n\nmodel empty {\n}\n").expect("schema to parse");
+    let schema = PrismaSchema::from(&file);
+    assert_eq!(schema.get_models().len(), 2);
+    assert!(squash(schema.as_text()).contains("model draft { }"));
+
+    let posts = table(
+        "posts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("author_id", "int", "NO", "MUL"),
+        ],
+        vec![foreign_key("fk_author", "author_id", "users", "id")],
+    );
+    let schema = PrismaSchema::from(&vec![users(), posts]);
+    let file = SchemaFile::parse(&schema.as_text()).expect("schema to parse");
+    assert!(PrismaSchema::from(&file) == schema);
+}
+
+#[test]
+pub fn prisma_schema_diff() {
+    let mut name = column("name", "varchar(64)", "YES", "");
+    name.default = Some("anonymous".to_string());
+    let posts = table(
+        "posts",
+        vec![column("id", "int", "NO", "PRI"), name],
+        vec![Key::Index(IndexKey {
+            constraint_name: "posts_name".to_string(),
+            column_name: "name".to_string(),
+        })],
+    );
+    let database = PrismaSchema::from(&vec![users(), posts]);
+    let disk_text = r#"generator client {
+  provider = "prisma-client-js"
+}
+
+datasource db {
+  provider = "mysql"
+  url      = env("DATABASE_URL")
+}
+
+model posts {
+  id     Int    @id
+  name   String @db.VarChar(32)
+  legacy Int
+}
+
+model sessions {
+  id Int @id
+}
+"#;
+    let disk = PrismaSchema::from(&SchemaFile::parse(disk_text).expect("schema to parse"));
+    let diff = SchemaDiff::between(&disk, &database);
+    assert_eq!(diff.added_models, vec!["users"]);
+    assert_eq!(diff.removed_models, vec!["sessions"]);
+    let posts = &diff.changed_models[0];
+    assert_eq!(posts.removed_fields, vec!["legacy"]);
+    assert!(posts.added_fields.is_empty());
+    assert_eq!(posts.changed_fields[0].name, "name");
+    assert!(posts.changed_fields[0].changes.contains(&AttributeChange {
+        attribute: "@db",
+        disk: Some("@db.VarChar(32)".to_string()),
+        database: Some("@db.VarChar(64)".to_string()),
+    }));
+    assert_eq!(
+        posts.added_directives,
+        vec!["@@index([name], map: \"posts_name\")"]
+    );
+    let report = diff.as_text();
+    assert!(report.contains("+ model users\n- model sessions\n~ model posts\n    - legacy\n"));
+    assert!(report.contains("        type: String -> String?\n"));
+    assert!(SchemaDiff::between(&database, &database).is_empty());
+}
+
+#[test]
+pub fn prisma_defaults() {
+    let with_default = |field: &str, type_: &str, default: Option<&str>, extra: &str| {
+        let mut description = column(field, type_, "NO", "");
+        description.default = default.map(str::to_string);
+        description.extra = extra.to_string();
+        description
+    };
+    let mut id = column("id", "int", "NO", "PRI");
+    id.extra = "auto_increment".to_string();
+    let events = table(
+        "events",
+        vec![
+            id,
+            with_default(
+                "created_at",
+                "datetime(3)",
+                Some("CURRENT_TIMESTAMP(3)"),
+                "DEFAULT_GENERATED",
+            ),
+            with_default(
+                "updated_at",
+                "timestamp",
+                Some("CURRENT_TIMESTAMP"),
+                "DEFAULT_GENERATED on update CURRENT_TIMESTAMP",
+            ),
+            with_default("token", "varchar(36)", Some("uuid()"), "DEFAULT_GENERATED"),
+            with_default("total", "int", Some("(1 + 2)"), "DEFAULT_GENERATED"),
+            with_default("title", "varchar(191)", Some("say \"hi\""), ""),
+            with_default("attempts", "int", Some("3"), ""),
+            with_default("active", "tinyint(1)", Some("1"), ""),
+            with_default("price", "decimal(10,2)", Some("10.50"), ""),
+            with_default("signature", "varbinary(16)", Some("abc"), ""),
+            with_default("rank", "int", Some("high"), ""),
+            with_default("origin", "point", Some("POINT(0 0)"), ""),
+        ],
+        vec![],
+    );
+    let schema = PrismaSchema::from(&vec![events]);
+    assert!(schema
+        .as_text()
+        .contains("@default(now()) @updatedAt @db.Timestamp(0)"));
+    let text = squash(schema.as_text());
+    assert!(text.contains("id Int @id @default(autoincrement())"));
+    assert!(text.contains("created_at DateTime @default(now()) updated_at"));
+    assert!(text.contains("updated_at DateTime @default(now()) @updatedAt @db.Timestamp(0)"));
+    // Expression defaults keep the parentheses MySQL requires around them.
+    assert!(text.contains("token String @default(dbgenerated(\"(uuid())\")) @db.VarChar(36)"));
+    assert!(text.contains("total Int @default(dbgenerated(\"(1 + 2)\"))"));
+    assert!(text.contains("title String @default(\"say \\\"hi\\\"\")"));
+    assert!(text.contains("attempts Int @default(3)"));
+    assert!(text.contains("active Boolean @default(true)"));
+    assert!(text.contains("price Decimal @default(10.50) @db.Decimal(10, 2)"));
+    assert!(text.contains("signature Bytes @default(dbgenerated(\"abc\")) @db.VarBinary(16)"));
+    assert!(text.contains("rank Int @default(dbgenerated(\"high\"))"));
+    assert!(text.contains("origin Unsupported(\"point\") @default(dbgenerated(\"POINT(0 0)\"))"));
+
+    let file = SchemaFile::parse(&schema.as_text()).expect("schema to parse");
+    assert!(PrismaSchema::from(&file) == schema);
+}
+
+#[test]
+pub fn prisma_options() {
+    let mut schema = PrismaSchema::from(&vec![users()]);
+    let mut client = Generator::new();
+    client.output = Some("../generated/client".to_string());
+    client.preview_features = vec!["fullTextSearch".to_string(), "metrics".to_string()];
+    client.binary_targets = vec!["native".to_string()];
+    let docs = Generator {
+        name: "docs".to_string(),
+        provider: "node node_modules/prisma-docs-generator".to_string(),
+        output: None,
+        preview_features: vec![],
+        binary_targets: vec![],
+    };
+    schema.apply_options(&PrismaOptions {
+        datasource: DatasourceOptions {
+            name: "main".to_string(),
+            url_env: "MAIN_URL".to_string(),
+            relation_mode: Some("prisma".to_string()),
+            shadow_database_url_env: Some("SHADOW_URL".to_string()),
+        },
+        generators: vec![client, docs],
+        layout: PrismaLayout::File,
+    });
+    let text = schema.as_text();
+    assert!(text.starts_with(
+        r#"generator client {
+  provider        = "prisma-client-js"
+  output          = "../generated/client"
+  previewFeatures = ["fullTextSearch", "metrics"]
+  binaryTargets   = ["native"]
+}
+
+generator docs {
+  provider = "node node_modules/prisma-docs-generator"
+}
+
+datasource main {
+  provider          = "mysql"
+  url               = env("MAIN_URL")
+  shadowDatabaseUrl = env("SHADOW_URL")
+  relationMode      = "prisma"
+}
+"#
+    ));
+    let file = SchemaFile::parse(&text).expect("schema to parse");
+    assert!(PrismaSchema::from(&file) == schema);
+
+    // Mappings saved before the options existed get the defaults.
+    let mappings = DiskMapping::from_json(serde_json::json!([
+        { "format": "Prisma", "path": "schema.prisma" }
+    ]))
+    .expect("mappings to load");
+    assert_eq!(mappings[0].prisma, PrismaOptions::default());
+}
+
+#[test]
+pub fn prisma_folder_layout() {
+    let id_only = |name: &str| Table {
+        name: name.to_string(),
+        ..users()
+    };
+    let posts = table(
+        "posts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("author", "int", "NO", "MUL"),
+            column("status", "enum('draft','published')", "NO", ""),
+        ],
+        vec![foreign_key("posts_ibfk_1", "author", "users", "id")],
+    );
+    let tables = vec![users(), posts, id_only("sessions"), id_only("tokens")];
+    let root = std::env::temp_dir().join(format!("prisma_folder_{}", std::process::id()));
+    let mapped = root.join("schema");
+    std::fs::create_dir_all(&mapped).expect("folder to be created");
+    std::fs::write(
+        mapped.join("schema.prisma"),
+        "model hand_written {\n  id Int @id\n}\n",
+    )
+    .expect("hand-written file to be written");
+    let mut translator = PrismaTranslator {
+        path: mapped.display().to_string(),
+        disk_schema: None,
+        db_schema: None,
+        naming: NamingPolicy::Database,
+        merge: false,
+        options: PrismaOptions {
+            layout: PrismaLayout::Folder {
+                groups: vec![ModelGroup {
+                    name: "auth".to_string(),
+                    tables: vec!["sessions".to_string(), "tokens".to_string()],
+                }],
+            },
+            ..PrismaOptions::default()
+        },
+    };
+    translator
+        .write_to_disk(&tables)
+        .expect("folder to be written");
+    // Without merging, the mapped folder is left alone and the output goes beside it.
+    let folder = root.join(OUTPUT_FOLDER);
+    let file_names = || {
+        let mut file_names: Vec<String> = std::fs::read_dir(&folder)
+            .expect("folder to be read")
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|file_name| file_name.ends_with(".prisma"))
+            .collect();
+        file_names.sort();
+        file_names
+    };
+    assert_eq!(
+        file_names(),
+        [
+            "auth.prisma",
+            "enums.prisma",
+            "posts.prisma",
+            "schema.prisma",
+            "users.prisma"
+        ]
+    );
+    assert!(std::fs::read_to_string(mapped.join("schema.prisma"))
+        .unwrap()
+        .contains("hand_written"));
+    let read = |file_name: &str| squash(std::fs::read_to_string(folder.join(file_name)).unwrap());
+    let config = read("schema.prisma");
+    assert!(config.contains("generator client"));
+    assert!(config.contains("datasource db"));
+    assert!(!config.contains("model"));
+    let auth = read("auth.prisma");
+    assert!(auth.contains("model sessions"));
+    assert!(auth.contains("model tokens"));
+    assert!(!auth.contains("datasource"));
+    assert!(
+        read("posts.prisma").contains("users users @relation(fields: [author], references: [id])")
+    );
+    assert!(read("enums.prisma").contains("enum posts_status { draft published }"));
+
+    // Files from dropped models are removed, but only the ones the tool wrote.
+    std::fs::write(folder.join("notes.prisma"), "// kept\n").expect("file to be written");
+    let tables = vec![users(), id_only("sessions"), id_only("tokens")];
+    translator
+        .write_to_disk(&tables)
+        .expect("folder to be rewritten");
+    assert_eq!(
+        file_names(),
+        [
+            "auth.prisma",
+            "notes.prisma",
+            "schema.prisma",
+            "users.prisma"
+        ]
+    );
+
+    translator.path = folder.display().to_string();
+    let from_disk = translator.parse_from_disk().expect("folder to parse");
+    std::fs::remove_dir_all(&root).expect("folder to be removed");
+    // The files are read in name order, so the models come back in a different order.
+    let translation = translator.get_translation(&tables);
+    assert_eq!(from_disk.get_models().len(), translation.get_models().len());
+    for model in translation.get_models() {
+        assert!(
+            from_disk.get_models().contains(model),
+            "{}",
+            model.get_name()
+        );
+    }
+    assert!(from_disk.get_enums() == translation.get_enums());
+}
+
+#[test]
+pub fn prisma_folder_merge() {
+    let posts = table(
+        "posts",
+        vec![
+            column("id", "int", "NO", "PRI"),
+            column("status", "enum('draft','published')", "NO", ""),
+        ],
+        vec![],
+    );
+    let root = std::env::temp_dir().join(format!("prisma_folder_merge_{}", std::process::id()));
+    std::fs::create_dir_all(&root).expect("folder to be created");
+    let write = |file_name: &str, text: &str| {
+        std::fs::write(root.join(file_name), text).expect("file to be written");
+    };
+    let schema_text = r#"generator client {
+  provider = "prisma-client-js"
+}
+
+model hand_written {
+  id   Int  @id
+  mood mood
+}
+"#;
+    write("schema.prisma", schema_text);
+    write(
+        "users.prisma",
+        "// kept\nmodel users {\n  id Int @id\n}\n\nmodel accounts {}\n",
+    );
+    write("tokens.prisma", "model tokens {\n  id Int @id\n}\n");
+    write(
+        "enums.prisma",
+        "enum mood {\n  happy\n}\n\nenum stale {\n  old\n}\n",
+    );
+    let translator = PrismaTranslator {
+        path: root.display().to_string(),
+        disk_schema: None,
+        db_schema: None,
+        naming: NamingPolicy::Database,
+        merge: true,
+        options: PrismaOptions {
+            layout: PrismaLayout::Folder { groups: vec![] },
+            ..PrismaOptions::default()
+        },
+    };
+    translator
+        .write_to_disk(&[users(), posts])
+        .expect("folder to merge");
+    let read = |file_name: &str| std::fs::read_to_string(root.join(file_name)).unwrap();
+    // Blocks written by hand outside the files the database's split would put them in stay.
+    assert_eq!(read("schema.prisma"), schema_text);
+    let users_text = squash(read("users.prisma"));
+    assert!(users_text.starts_with("// kept model users { id Int @id }"));
+    assert!(users_text.ends_with("model accounts {}"));
+    assert!(!read("tokens.prisma").contains("tokens"));
+    let enums = squash(read("enums.prisma"));
+    assert!(enums.contains("enum mood { happy }"));
+    assert!(enums.contains("enum posts_status { draft published }"));
+    assert!(!enums.contains("stale"));
+    assert!(squash(read("posts.prisma")).contains("status posts_status"));
+    std::fs::remove_dir_all(&root).expect("folder to be removed");
+}
+
+#[test]
+pub fn prisma_documentation() {
+    let posts = || Table {
+        comment: Some("Published writing".to_string()),
+        ..table(
+            "posts",
+            vec![
+                column("id", "int", "NO", "PRI"),
+                Description {
+                    comment: Some("Shown in listings.\nKeep it short.".to_string()),
+                    ..column("title", "varchar(191)", "NO", "")
+                },
+            ],
+            vec![],
+        )
+    };
+    let schema = PrismaSchema::from(&vec![posts()]);
+    let text = schema.as_text();
+    assert!(text.contains(
+        "/// Published writing\nmodel posts {\n  id    Int    @id \n  /// Shown in listings.\n  /// Keep it short.\n  title String"
+    ));
+    let file = SchemaFile::parse(&text).expect("schema to parse");
+    assert!(PrismaSchema::from(&file) == schema);
+
+    // Documentation on disk is kept, and new fields bring theirs along.
+    let disk_text = "model posts {\n  id    Int    @id\n  /// Written by hand\n  title String\n}\n";
+    let mut with_body = posts();
+    with_body.description.push(Description {
+        comment: Some("The post itself".to_string()),
+        ..column("body", "text", "NO", "")
+    });
+    let merged = merge(disk_text, &PrismaSchema::from(&vec![with_body])).expect("schema to merge");
+    assert!(merged.contains("  /// Written by hand\n  title String"));
+    assert!(merged.contains("  /// The post itself\n  body  String @db.Text"));
+    assert!(!merged.contains("Shown in listings"));
+
+    let diff = SchemaDiff::between(
+        &PrismaSchema::from(&file),
+        &PrismaSchema::from(&vec![posts()]),
+    );
+    assert!(diff.is_empty());
+}
+
+#[test]
+pub fn prisma_to_tables() {
+    let tables = mysql_translate::remotes::ddl::parse_tables(
+        "
+CREATE TABLE `users` (
+  `id` int unsigned NOT NULL AUTO_INCREMENT,
+  `email` varchar(255) NOT NULL,
+  `is_admin` tinyint(1) NOT NULL DEFAULT '0',
+  `updated_at` timestamp NULL DEFAULT NULL ON UPDATE CURRENT_TIMESTAMP,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `users_email_key` (`email`)
+) COMMENT='People who can sign in';
+CREATE TABLE `posts` (
+  `id` int NOT NULL AUTO_INCREMENT,
+  `author_id` int unsigned NOT NULL COMMENT 'Who wrote the post',
+  `status` enum('draft','in review') NOT NULL DEFAULT 'in review',
+  `title` varchar(191) NOT NULL DEFAULT 'Untitled \"draft\"',
+  `price` decimal(10,2) DEFAULT NULL,
+  `created_at` datetime(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `posts_author_title_key` (`author_id`,`title`),
+  KEY `posts_status_idx` (`status`),
+  CONSTRAINT `posts_author_fk` FOREIGN KEY (`author_id`) REFERENCES `users` (`id`) ON DELETE CASCADE
+);
+",
+    )
+    .expect("dump to parse");
+    for naming in [NamingPolicy::Database, NamingPolicy::Conventional] {
+        let text = PrismaSchema::from_tables(&tables, naming).as_text();
+        let file = SchemaFile::parse(&text).expect("schema to parse");
+        let read_back = PrismaSchema::from(&file)
+            .to_tables()
+            .expect("schema to read back");
+        assert_eq!(
+            PrismaSchema::from_tables(&read_back, naming).as_text(),
+            text
+        );
+        let posts = read_back
+            .iter()
+            .find(|table| table.name == "posts")
+            .unwrap();
+        assert_eq!(posts.description[1].field, "author_id");
+        assert_eq!(posts.description[1].type_, "int unsigned");
+        assert_eq!(posts.description[2].type_, "enum('draft','in review')");
+        assert_eq!(posts.description[2].default.as_deref(), Some("in review"));
+        assert!(posts.keys.keys.iter().any(|key| matches!(
+            key,
+            Key::Foreign(foreign_key) if foreign_key.constraint_name == "posts_author_id_fkey"
+                && foreign_key.on_delete == Some(ReferentialAction::Cascade)
+        )));
+    }
+
+    let mut translator = PrismaTranslator {
+        path: String::new(),
+        disk_schema: None,
+        db_schema: None,
+        naming: NamingPolicy::Database,
+        merge: false,
+        options: PrismaOptions::default(),
+    };
+    assert!(translator.to_tables().is_err());
+    translator.disk_schema = Some(PrismaSchema::from(&tables));
+    assert_eq!(translator.to_tables().unwrap().len(), 2);
+}
+
+#[test]
+pub fn prisma_convert() {
+    let folder = std::env::temp_dir().join(format!("prisma_convert_{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    let mapping = |format: &str, file: &str| {
+        DiskMapping::from_json(serde_json::json!([{
+            "format": format,
+            "path": folder.join(file).to_str().unwrap(),
+            "merge": true,
+        }]))
+        .unwrap()
+        .remove(0)
+    };
+    let database = Database {
+        name: "blog".to_string(),
+        db_url: "blog.sql".to_string(),
+        source: RemoteSource::Ddl,
+        disk_mappings: vec![
+            mapping("Prisma", "schema.prisma"),
+            mapping("Json", "schema.json"),
+        ],
+    };
+    let tables = vec![users()];
+    let text = PrismaSchema::from(&tables).as_text();
+    std::fs::write(folder.join("schema.prisma"), &text).unwrap();
+
+    let registry = TranslatorRegistry::default();
+    database
+        .convert(&registry, AcceptedFormat::Prisma, AcceptedFormat::Json)
+        .unwrap();
+    let json = std::fs::read_to_string(folder.join("schema.json")).unwrap();
+    assert!(json.contains("\"name\": \"users\""));
+
+    std::fs::remove_file(folder.join("schema.prisma")).unwrap();
+    database
+        .convert(&registry, AcceptedFormat::Json, AcceptedFormat::Prisma)
+        .unwrap();
+    let converted = std::fs::read_to_string(folder.join("schema.prisma")).unwrap();
+    std::fs::remove_dir_all(&folder).unwrap();
+    assert_eq!(squash(converted), squash(text));
+}
+
+#[test]
+pub fn prisma_unreadable_database() {
+    let folder = std::env::temp_dir().join(format!("prisma_unreadable_{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    let path = folder.join("schema.prisma");
+    std::fs::write(&path, PrismaSchema::from(&vec![users()]).as_text()).unwrap();
+    let mapping = DiskMapping::from_json(serde_json::json!([{
+        "format": "Prisma",
+        "path": path.to_str().unwrap(),
+    }]))
+    .unwrap();
+    // missing.sql doesn't exist, so the database can't be read.
+    let session = Session {
+        databases: vec![Database {
+            name: "blog".to_string(),
+            db_url: folder.join("missing.sql").display().to_string(),
+            source: RemoteSource::Ddl,
+            disk_mappings: mapping,
+        }],
+        _data_location: String::new(),
+        command_line_flags: None,
+        registry: TranslatorRegistry::default(),
+    };
+    let options = ["prisma".to_string()];
+    let shown = session.get_current_local_database(0, 0, &options);
+    let viewed = session.view_table_from_database(0, 0, &options);
+    let written = session.write_one_schema_from_database(0, 0, &options);
+    let synced = session.sync();
+    std::fs::remove_dir_all(&folder).unwrap();
+    // The schema on disk is shown with why it wasn't compared, and the rest fail with the error.
+    let shown = shown.unwrap();
+    assert!(shown.starts_with("disk schema:"));
+    assert!(shown.contains("couldn't read the database to compare with"));
+    assert!(viewed.is_err());
+    assert!(written.is_err());
+    assert!(synced.is_err());
+}
+
+#[test]
+pub fn prisma_unsupported_warnings() {
+    let places = |dialect: Dialect, types: &[&str]| Table {
+        dialect,
+        ..table(
+            "places",
+            types
+                .iter()
+                .enumerate()
+                .map(|(i, type_)| column(&format!("c{}", i), type_, "NO", ""))
+                .collect(),
+            vec![],
+        )
+    };
+    let postgres = places(
+        Dialect::Postgres,
+        &["character varying(255)", "uuid", "jsonb", "integer[]"],
+    );
+    assert!(PrismaSchema::from(&vec![postgres]).warnings().is_empty());
+    let sqlite = places(Dialect::Sqlite, &["STRING", "INTEGER", "DATETIME"]);
+    assert!(PrismaSchema::from(&vec![sqlite]).warnings().is_empty());
+    let mysql = places(Dialect::MySql, &["int", "point"]);
+    assert_eq!(
+        PrismaSchema::from(&vec![mysql]).warnings(),
+        ["places.c1 has a type Prisma doesn't support, writing it as Unsupported(\"point\")"]
+    );
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use anyhow::Result;
+use common::users;
+use mysql_translate::{
+    functionality::{
+        database::Database,
+        session::Session,
+        structure::{AcceptedFormat, DiskMapping, RemoteSource},
+    },
+    remotes::sql::Table,
+    translators::{behaviour::TranslatorBehaviour, registry::TranslatorRegistry},
+};
+
+/// A translator writing each table's column names on a line.
+struct CsvTranslator {
+    lines: Vec<String>,
+}
+
+impl TranslatorBehaviour for CsvTranslator {
+    type Translation = Vec<String>;
+
+    fn write_to_disk(&self, _descriptions: &[Table]) -> Result<()> {
+        Ok(())
+    }
+    fn get_translation(&self, descriptions: &[Table]) -> Vec<String> {
+        descriptions
+            .iter()
+            .map(|table| {
+                let columns: Vec<&str> = table
+                    .description
+                    .iter()
+                    .map(|description| description.field.as_str())
+                    .collect();
+                format!("{},{}", table.name, columns.join(","))
+            })
+            .collect()
+    }
+    fn load_from_database(&mut self, descriptions: &[Table]) {
+        self.lines = self.get_translation(descriptions);
+    }
+    fn load_from_disk(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn get_string(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+#[test]
+pub fn registry_translators() {
+    let mut registry = TranslatorRegistry::default();
+    assert_eq!(registry.formats(), vec!["json", "prisma"]);
+    assert_eq!(registry.format("Prisma"), Some(AcceptedFormat::Prisma));
+    assert_eq!(registry.format("csv"), None);
+
+    let mapping = DiskMapping::from_json(serde_json::json!([{ "format": "Json", "path": "" }]))
+        .unwrap()
+        .remove(0);
+    assert_eq!(mapping.format, AcceptedFormat::Json);
+    assert_eq!(serde_json::to_value(&mapping.format).unwrap(), "json");
+
+    // Formats are only checked against the registry when their translator is looked up.
+    let path = std::env::temp_dir().join(format!("registry_{}.sql", std::process::id()));
+    std::fs::write(
+        &path,
+        "CREATE TABLE `users` (`id` int NOT NULL, PRIMARY KEY (`id`));",
+    )
+    .unwrap();
+    let csv_mapping = DiskMapping::from_json(serde_json::json!([{ "format": "csv", "path": "" }]))
+        .unwrap()
+        .remove(0);
+    assert_eq!(csv_mapping.format, AcceptedFormat::Other("csv".to_string()));
+    let database = Database {
+        name: "blog".to_string(),
+        db_url: path.to_str().unwrap().to_string(),
+        source: RemoteSource::Ddl,
+        disk_mappings: vec![csv_mapping],
+    };
+    assert!(database
+        .translator(&registry, &database.disk_mappings[0])
+        .is_err());
+
+    registry.register("csv", |_| Box::new(CsvTranslator { lines: vec![] }));
+    assert_eq!(
+        registry.format("CSV"),
+        Some(AcceptedFormat::Other("csv".to_string()))
+    );
+    let mut translator = registry.translator("csv", &mapping).unwrap();
+    translator.load_from_database(&[users()]);
+    assert_eq!(translator.get_string(), "users,id");
+    assert!(translator.to_tables().is_err());
+
+    // A mapping picks up a translator registered with the session.
+    let options: Vec<String> = registry.formats().into_iter().map(str::to_string).collect();
+    let session = Session {
+        databases: vec![database],
+        _data_location: String::new(),
+        command_line_flags: None,
+        registry,
+    };
+    let shown = session.view_table_from_database(2, 0, &options);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(shown.unwrap(), "users,id");
+}
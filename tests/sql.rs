@@ -0,0 +1,200 @@
+use mysql_translate::remotes::sql::{
+    assemble_tables, Character, ColumnRow, ColumnType, Description, ForeignKey, IndexKey, Key,
+    KeyColumnUsageRow, Numeric, ReferentialAction, ReferentialConstraintRow, StatisticsRow,
+    TableCommentRow, TableConstraintRow, TableKeys, UniqueKey,
+};
+
+#[test]
+fn column_type_parsing() {
+    assert_eq!(
+        ColumnType::from("decimal(10,2) unsigned zerofill"),
+        ColumnType::Decimal(Numeric {
+            precision: Some(10),
+            scale: Some(2),
+            unsigned: true,
+            zerofill: true,
+        })
+    );
+    assert_eq!(
+        ColumnType::from("bigint"),
+        ColumnType::BigInt(Numeric::default())
+    );
+    assert_eq!(
+        ColumnType::from("varchar(255) CHARACTER SET utf8mb4"),
+        ColumnType::VarChar(Character {
+            length: Some(255),
+            charset: Some("utf8mb4".to_string()),
+        })
+    );
+    assert_eq!(
+        ColumnType::from("enum('draft','it''s, fine')"),
+        ColumnType::Enum {
+            values: vec!["draft".to_string(), "it's, fine".to_string()],
+            charset: None,
+        }
+    );
+    assert_eq!(
+        ColumnType::from("datetime(3)"),
+        ColumnType::DateTime(Some(3))
+    );
+    assert_eq!(ColumnType::from("point"), ColumnType::Point);
+}
+
+#[test]
+fn column_type_display() {
+    for type_ in [
+        "int unsigned",
+        "tinyint(1)",
+        "decimal(10,2)",
+        "varchar(255)",
+        "timestamp(3)",
+        "enum('a','it''s')",
+        "set('x','y')",
+        "geometry",
+    ] {
+        assert_eq!(ColumnType::from(type_).to_string(), type_);
+    }
+}
+
+#[test]
+fn schema_rows_assembled_into_tables() {
+    let s = |value: &str| value.to_string();
+    let column = |table: &str, field: &str, key: &str, comment: &str| ColumnRow {
+        table_name: s(table),
+        description: Description {
+            field: s(field),
+            type_: s("int"),
+            null: s("NO"),
+            key: s(key),
+            default: None,
+            extra: s(""),
+            comment: Some(s(comment)).filter(|comment| !comment.is_empty()),
+        },
+    };
+    let constraint = |table: &str, name: &str, type_: &str| TableConstraintRow {
+        table_name: s(table),
+        constraint_name: s(name),
+        constraint_type: s(type_),
+    };
+    let usage =
+        |table: &str, name: &str, column: &str, referenced: Option<&str>| KeyColumnUsageRow {
+            table_name: s(table),
+            constraint_name: s(name),
+            column_name: s(column),
+            ordinal_position: Some(1),
+            referenced_table_name: referenced.map(|_| s("users")),
+            referenced_column_name: referenced.map(s),
+        };
+    let statistic = |table: &str, name: &str, column: &str, seq: usize| StatisticsRow {
+        table_name: s(table),
+        index_name: s(name),
+        column_name: Some(s(column)),
+        seq_in_index: Some(seq),
+        cardinality: None,
+        index_type: None,
+    };
+    let tables = assemble_tables(
+        vec![
+            column("posts", "id", "PRI", ""),
+            column("posts", "author_id", "MUL", ""),
+            column("posts", "slug", "UNI", "Where the post lives"),
+            column("posts", "title", "MUL", ""),
+            column("posts", "created_at", "", ""),
+            column("users", "id", "PRI", ""),
+            column("users", "email", "UNI", ""),
+        ],
+        vec![
+            statistic("users", "PRIMARY", "id", 1),
+            statistic("users", "users_email_unique", "email", 1),
+            statistic("posts", "PRIMARY", "id", 1),
+            statistic("posts", "posts_slug_unique", "slug", 1),
+            statistic("posts", "posts_author_fk", "author_id", 1),
+            statistic("posts", "posts_title_created", "created_at", 2),
+            statistic("posts", "posts_title_created", "title", 1),
+        ],
+        vec![
+            usage("users", "PRIMARY", "id", None),
+            usage("users", "users_email_unique", "email", None),
+            usage("posts", "PRIMARY", "id", None),
+            usage("posts", "posts_slug_unique", "slug", None),
+            usage("posts", "posts_author_fk", "author_id", Some("id")),
+        ],
+        vec![
+            constraint("users", "PRIMARY", "PRIMARY KEY"),
+            constraint("users", "users_email_unique", "UNIQUE"),
+            constraint("posts", "PRIMARY", "PRIMARY KEY"),
+            constraint("posts", "posts_slug_unique", "UNIQUE"),
+            constraint("posts", "posts_author_fk", "FOREIGN KEY"),
+        ],
+        vec![ReferentialConstraintRow {
+            table_name: s("posts"),
+            constraint_name: s("posts_author_fk"),
+            update_rule: s("CASCADE"),
+            delete_rule: s("RESTRICT"),
+        }],
+        vec![
+            TableCommentRow {
+                table_name: s("posts"),
+                comment: s(""),
+            },
+            TableCommentRow {
+                table_name: s("users"),
+                comment: s("Everyone who can sign in"),
+            },
+        ],
+    );
+
+    // Tables come out in the order of their columns, each with its own columns and comment.
+    let names: Vec<&str> = tables.iter().map(|table| table.name.as_str()).collect();
+    assert_eq!(names, ["posts", "users"]);
+    let fields: Vec<&str> = tables[0]
+        .description
+        .iter()
+        .map(|description| description.field.as_str())
+        .collect();
+    assert_eq!(fields, ["id", "author_id", "slug", "title", "created_at"]);
+    assert_eq!(
+        tables[0].description[2].comment.as_deref(),
+        Some("Where the post lives")
+    );
+    assert_eq!(tables[0].comment, None);
+    assert_eq!(
+        tables[1].comment.as_deref(),
+        Some("Everyone who can sign in")
+    );
+
+    // The keys are the ones the per-table key queries read.
+    let index = |column: &str| IndexKey {
+        constraint_name: s("posts_title_created"),
+        column_name: s(column),
+    };
+    let posts_keys = TableKeys {
+        keys: vec![
+            Key::Unique(vec![UniqueKey {
+                constraint_name: s("posts_slug_unique"),
+                column_names: vec![s("slug")],
+            }]),
+            Key::Index(IndexKey {
+                constraint_name: s("posts_author_fk"),
+                column_name: s("author_id"),
+            }),
+            Key::MultiIndex(vec![index("title"), index("created_at")]),
+            Key::Foreign(ForeignKey {
+                constraint_name: s("posts_author_fk"),
+                column_names: vec![s("author_id")],
+                referenced_table_name: s("users"),
+                referenced_column_names: vec![s("id")],
+                on_delete: Some(ReferentialAction::Restrict),
+                on_update: Some(ReferentialAction::Cascade),
+            }),
+        ],
+    };
+    let users_keys = TableKeys {
+        keys: vec![Key::Unique(vec![UniqueKey {
+            constraint_name: s("users_email_unique"),
+            column_names: vec![s("email")],
+        }])],
+    };
+    assert_eq!(format!("{:?}", tables[0].keys), format!("{:?}", posts_keys));
+    assert_eq!(format!("{:?}", tables[1].keys), format!("{:?}", users_keys));
+}
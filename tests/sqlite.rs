@@ -0,0 +1,80 @@
+use mysql_translate::remotes::{
+    sql::{Dialect, Key, ReferentialAction},
+    sqlite::get_table_descriptions,
+};
+use mysql_translate::translators::prisma::schema::PrismaSchema;
+use rusqlite::Connection;
+
+static SCHEMA: &str = "
+CREATE TABLE users (
+    id INTEGER PRIMARY KEY,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    status TEXT NOT NULL DEFAULT 'draft',
+    balance DECIMAL(10,2),
+    is_admin BOOLEAN NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE TABLE memberships (
+    team_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL REFERENCES users ON DELETE CASCADE,
+    note TEXT,
+    PRIMARY KEY (team_id, user_id)
+);
+CREATE INDEX memberships_note_idx ON memberships (note, user_id);
+";
+
+#[test]
+fn sqlite_introspection() {
+    let path = std::env::temp_dir().join(format!("sqlite_introspection_{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    Connection::open(&path)
+        .expect("database to be created")
+        .execute_batch(SCHEMA)
+        .expect("schema to apply");
+
+    let url = format!("sqlite://{}", path.display());
+    let tables = get_table_descriptions(&url).expect("introspection to succeed");
+    std::fs::remove_file(&path).expect("database to be removed");
+    assert_eq!(tables[0].name, "memberships");
+    assert_eq!(tables[1].name, "users");
+    let (memberships, users) = (&tables[0], &tables[1]);
+    assert_eq!(users.dialect, Dialect::Sqlite);
+
+    let id = &users.description[0];
+    assert_eq!(id.key, "PRI");
+    assert_eq!(id.null, "NO");
+    assert_eq!(id.extra, "auto_increment");
+    assert_eq!(users.description[1].key, "UNI");
+    assert_eq!(users.description[2].default.as_deref(), Some("draft"));
+    assert_eq!(
+        users.description[5].default.as_deref(),
+        Some("CURRENT_TIMESTAMP")
+    );
+
+    let foreign_key = memberships
+        .keys
+        .keys
+        .iter()
+        .find_map(|key| match key {
+            Key::Foreign(foreign_key) => Some(foreign_key),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(foreign_key.constraint_name, "memberships_user_id_fkey");
+    assert_eq!(foreign_key.column_names, vec!["user_id"]);
+    assert_eq!(foreign_key.referenced_column_names, vec!["id"]);
+    assert_eq!(foreign_key.on_delete, Some(ReferentialAction::Cascade));
+    assert_eq!(foreign_key.on_update, Some(ReferentialAction::NoAction));
+    assert!(memberships.keys.keys.iter().any(|key| matches!(
+        key,
+        Key::MultiIndex(index_keys) if index_keys[0].constraint_name == "memberships_note_idx"
+    )));
+
+    let schema = PrismaSchema::from(&tables).as_text();
+    let squashed: String = schema.split_whitespace().collect::<Vec<&str>>().join(" ");
+    assert!(squashed.contains("provider = \"sqlite\""));
+    assert!(squashed.contains("balance Decimal?"));
+    assert!(squashed.contains("is_admin Boolean @default(false)"));
+    assert!(squashed.contains("created_at DateTime @default(now())"));
+    assert!(!squashed.contains("@db."));
+}